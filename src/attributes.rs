@@ -0,0 +1,462 @@
+//!
+//! Named attribute channels for mesh components.
+//!
+//! Each channel is a `Vec` holding one value per component of its domain.
+//! The mesh keeps every channel in lockstep with the component lists, so
+//! values follow their component through the `swap_remove` performed by the
+//! removal methods and through `Mesh::compact`.
+//!
+//! Channels are borrowed from the mesh as an `AttributeRef` or
+//! `AttributeMut`, which check the generation of every index they are given,
+//! so a stale index never addresses the value of another component.
+//!
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::{Add, Deref, DerefMut, Index, IndexMut, Mul, Sub};
+use std::slice;
+
+use super::*;
+
+
+/// The kind of component an attribute channel stores values for.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AttributeDomain {
+    /// One value per vertex, addressed by `VertexIndex`.
+    Vertex,
+    /// One value per half-edge, addressed by `EdgeIndex`.
+    Edge,
+    /// One value per pair of twin edges.
+    ///
+    /// Values are stored for each half-edge and addressed by `EdgeIndex`, but
+    /// `Mesh::set_undirected_edge_attribute` writes both halves at once.
+    UndirectedEdge,
+    /// One value per face, addressed by `FaceIndex`.
+    Face,
+}
+
+/// Implemented by the index types of mesh components, allowing them to
+/// address values in an `Attribute`.
+pub trait ComponentIndex: Copy {
+    /// The position of the component in its list.
+    fn offset(self) -> usize;
+    /// The generation of the slot at the time the index was handed out.
+    fn generation(self) -> usize;
+}
+
+impl<Ix: IndexType> ComponentIndex for VertexIndex<Ix> {
+    fn offset(self) -> usize {
+        self.0.index()
+    }
+
+    fn generation(self) -> usize {
+        self.1.index()
+    }
+}
+
+impl<Ix: IndexType> ComponentIndex for EdgeIndex<Ix> {
+    fn offset(self) -> usize {
+        self.0.index()
+    }
+
+    fn generation(self) -> usize {
+        self.1.index()
+    }
+}
+
+impl<Ix: IndexType> ComponentIndex for FaceIndex<Ix> {
+    fn offset(self) -> usize {
+        self.0.index()
+    }
+
+    fn generation(self) -> usize {
+        self.1.index()
+    }
+}
+
+/// Computes the value of a component created between two others, such as
+/// the vertex inserted by `Mesh::split_edge`, with `t` running from the
+/// first value to the second.
+pub type Interpolation<T> = fn(&T, &T, f32) -> T;
+
+/// Picks whichever of the two values is closest to `t`.
+///
+/// This is the interpolation used by new channels, since it works for
+/// values of every type.
+pub fn nearest_interpolation<T: Clone>(a: &T, b: &T, t: f32) -> T {
+    if t < 0.5 { a.clone() } else { b.clone() }
+}
+
+/// Blends linearly between the two values.
+pub fn linear_interpolation<T>(a: &T, b: &T, t: f32) -> T
+    where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>
+{
+    *a + (*b - *a) * t
+}
+
+/// A typed attribute channel holding one value per component, in the order
+/// of the component list.
+///
+/// The channel itself knows nothing about generations, so values are only
+/// exposed by offset here. Component indices are resolved by the
+/// `AttributeRef` and `AttributeMut` views which dereference to it.
+#[derive(Debug, Clone)]
+pub struct Attribute<T> {
+    values: Vec<T>,
+    default: T,
+    interpolation: Interpolation<T>
+}
+
+impl<T> Attribute<T> {
+    /// The number of values, including the one for the default component.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the channel holds no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over all values, starting with the value of the
+    /// default component.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// The values as a slice, indexed by component offset.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The value given to components added after the channel was created.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Sets how values are derived for components which the mesh operators
+    /// create between existing ones.
+    ///
+    /// ```
+    /// use hedge::{AttributeDomain, Mesh, linear_interpolation};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// mesh.add_attribute::<f32>(AttributeDomain::Vertex, "temperature");
+    /// if let Some(mut temperatures) = mesh.attribute_mut::<f32>(AttributeDomain::Vertex, "temperature") {
+    ///     temperatures.set_interpolation(linear_interpolation);
+    /// }
+    /// ```
+    pub fn set_interpolation(&mut self, interpolation: Interpolation<T>) {
+        self.interpolation = interpolation;
+    }
+}
+
+/// Returns the offset of the index when it refers to the component which
+/// currently occupies its slot.
+fn current_offset<I: ComponentIndex, Ix: IndexType>(slots: &Slots<Ix>, index: I) -> Option<usize> {
+    let offset = index.offset();
+    if slots.generation(offset).index() == index.generation() && !slots.is_dead(offset) {
+        Some(offset)
+    } else {
+        None
+    }
+}
+
+/// An attribute channel borrowed from a mesh.
+///
+/// Indexing with a stale index, referring to a component which has since
+/// been removed or moved, panics; `get` returns `None` instead.
+pub struct AttributeRef<'mesh, T: 'mesh, Ix: IndexType + 'mesh = usize> {
+    attribute: &'mesh Attribute<T>,
+    slots: &'mesh Slots<Ix>
+}
+
+impl<'mesh, T, Ix: IndexType> AttributeRef<'mesh, T, Ix> {
+    /// Returns the value for the specified component, or `None` when the
+    /// index is out of bounds or stale.
+    pub fn get<I: ComponentIndex>(&self, index: I) -> Option<&'mesh T> {
+        let attribute = self.attribute;
+        current_offset(self.slots, index).and_then(|offset| attribute.values.get(offset))
+    }
+}
+
+impl<'mesh, T, Ix: IndexType> Clone for AttributeRef<'mesh, T, Ix> {
+    fn clone(&self) -> AttributeRef<'mesh, T, Ix> {
+        *self
+    }
+}
+
+impl<'mesh, T, Ix: IndexType> Copy for AttributeRef<'mesh, T, Ix> {}
+
+impl<'mesh, T, Ix: IndexType> Deref for AttributeRef<'mesh, T, Ix> {
+    type Target = Attribute<T>;
+
+    fn deref(&self) -> &Attribute<T> {
+        self.attribute
+    }
+}
+
+impl<'mesh, T, Ix: IndexType, I: ComponentIndex> Index<I> for AttributeRef<'mesh, T, Ix> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        self.get(index).expect("stale or out of bounds component index")
+    }
+}
+
+/// A mutable attribute channel borrowed from a mesh, checking indices like
+/// `AttributeRef` does.
+pub struct AttributeMut<'mesh, T: 'mesh, Ix: IndexType + 'mesh = usize> {
+    attribute: &'mesh mut Attribute<T>,
+    slots: &'mesh Slots<Ix>
+}
+
+impl<'mesh, T, Ix: IndexType> AttributeMut<'mesh, T, Ix> {
+    /// Returns the value for the specified component, or `None` when the
+    /// index is out of bounds or stale.
+    pub fn get<I: ComponentIndex>(&self, index: I) -> Option<&T> {
+        current_offset(self.slots, index).and_then(|offset| self.attribute.values.get(offset))
+    }
+
+    /// Obtains a mutable reference to the value for the specified component,
+    /// or `None` when the index is out of bounds or stale.
+    pub fn get_mut<I: ComponentIndex>(&mut self, index: I) -> Option<&mut T> {
+        match current_offset(self.slots, index) {
+            Some(offset) => self.attribute.values.get_mut(offset),
+            None => None,
+        }
+    }
+}
+
+impl<'mesh, T, Ix: IndexType> Deref for AttributeMut<'mesh, T, Ix> {
+    type Target = Attribute<T>;
+
+    fn deref(&self) -> &Attribute<T> {
+        self.attribute
+    }
+}
+
+impl<'mesh, T, Ix: IndexType> DerefMut for AttributeMut<'mesh, T, Ix> {
+    fn deref_mut(&mut self) -> &mut Attribute<T> {
+        self.attribute
+    }
+}
+
+impl<'mesh, T, Ix: IndexType, I: ComponentIndex> Index<I> for AttributeMut<'mesh, T, Ix> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        self.get(index).expect("stale or out of bounds component index")
+    }
+}
+
+impl<'mesh, T, Ix: IndexType, I: ComponentIndex> IndexMut<I> for AttributeMut<'mesh, T, Ix> {
+    fn index_mut(&mut self, index: I) -> &mut T {
+        self.get_mut(index).expect("stale or out of bounds component index")
+    }
+}
+
+/// Type erased operations needed to keep a channel in step with its list.
+trait Channel {
+    fn push_default(&mut self);
+    fn swap_remove(&mut self, offset: usize);
+    fn copy(&mut self, target: usize, source: usize);
+    fn compact(&mut self, offsets: &[usize]);
+    fn interpolate(&mut self, target: usize, a: usize, b: usize, t: f32);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Clone + 'static> Channel for Attribute<T> {
+    fn push_default(&mut self) {
+        self.values.push(self.default.clone());
+    }
+
+    fn swap_remove(&mut self, offset: usize) {
+        if offset < self.values.len() {
+            self.values.swap_remove(offset);
+        }
+    }
+
+    fn copy(&mut self, target: usize, source: usize) {
+        if target < self.values.len() && source < self.values.len() {
+            self.values[target] = self.values[source].clone();
+        }
+    }
+
+    fn compact(&mut self, offsets: &[usize]) {
+        compact::retain_live(&mut self.values, offsets);
+    }
+
+    fn interpolate(&mut self, target: usize, a: usize, b: usize, t: f32) {
+        let len = self.values.len();
+        if target < len && a < len && b < len {
+            self.values[target] = (self.interpolation)(&self.values[a], &self.values[b], t);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// All attribute channels of a mesh, keyed by domain and name.
+#[derive(Default)]
+pub(crate) struct AttributeRegistry {
+    channels: HashMap<(AttributeDomain, String), Box<dyn Channel>>
+}
+
+impl AttributeRegistry {
+    /// Appends a default value to every channel of the domain.
+    pub(crate) fn push(&mut self, domain: AttributeDomain) {
+        for (key, channel) in &mut self.channels {
+            if key.0 == domain {
+                channel.push_default();
+            }
+        }
+    }
+
+    /// Removes a value from every channel of the domain, moving the last
+    /// value into its place.
+    pub(crate) fn swap_remove(&mut self, domain: AttributeDomain, offset: usize) {
+        for (key, channel) in &mut self.channels {
+            if key.0 == domain {
+                channel.swap_remove(offset);
+            }
+        }
+    }
+
+    /// Overwrites the values of `target` with those of `source` in every
+    /// channel of the domain.
+    pub(crate) fn copy(&mut self, domain: AttributeDomain, target: usize, source: usize) {
+        for (key, channel) in &mut self.channels {
+            if key.0 == domain {
+                channel.copy(target, source);
+            }
+        }
+    }
+
+    /// Drops the values of tombstones from every channel of the domain, given
+    /// the offsets returned by `Slots::compact`.
+    pub(crate) fn compact(&mut self, domain: AttributeDomain, offsets: &[usize]) {
+        for (key, channel) in &mut self.channels {
+            if key.0 == domain {
+                channel.compact(offsets);
+            }
+        }
+    }
+
+    /// Sets the values of `target` in every channel of the domain by
+    /// interpolating between the values of `a` and `b`.
+    pub(crate) fn interpolate(&mut self, domain: AttributeDomain, target: usize, a: usize, b: usize, t: f32) {
+        for (key, channel) in &mut self.channels {
+            if key.0 == domain {
+                channel.interpolate(target, a, b, t);
+            }
+        }
+    }
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Adds a new attribute channel for the components of `domain`, filled
+    /// with default values.
+    ///
+    /// Returns false, leaving the existing channel untouched, when the domain
+    /// already has a channel of the same name.
+    ///
+    /// ```
+    /// use hedge::{AttributeDomain, Mesh, Vertex};
+    /// let mut mesh = Mesh::new();
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    ///
+    /// mesh.add_attribute::<f32>(AttributeDomain::Vertex, "weight");
+    /// if let Some(mut weights) = mesh.attribute_mut::<f32>(AttributeDomain::Vertex, "weight") {
+    ///     weights[v1] = 0.5;
+    /// }
+    /// assert_eq!(mesh.attribute::<f32>(AttributeDomain::Vertex, "weight").unwrap()[v1], 0.5);
+    /// ```
+    pub fn add_attribute<T: Clone + Default + 'static>(&mut self, domain: AttributeDomain, name: &str) -> bool {
+        self.add_attribute_with_default(domain, name, T::default())
+    }
+
+    /// Adds a new attribute channel for the components of `domain`, filled
+    /// with `default`, which is also given to components added later on.
+    ///
+    /// Returns false, leaving the existing channel untouched, when the domain
+    /// already has a channel of the same name.
+    pub fn add_attribute_with_default<T: Clone + 'static>(&mut self, domain: AttributeDomain, name: &str, default: T) -> bool {
+        let key = (domain, name.to_string());
+        if self.attributes.channels.contains_key(&key) {
+            return false;
+        }
+        let len = match domain {
+            AttributeDomain::Vertex => self.vertex_list.len(),
+            AttributeDomain::Edge | AttributeDomain::UndirectedEdge => self.edge_list.len(),
+            AttributeDomain::Face => self.face_list.len(),
+        };
+        let channel = Attribute {
+            values: vec![default.clone(); len],
+            default,
+            interpolation: nearest_interpolation
+        };
+        self.attributes.channels.insert(key, Box::new(channel));
+        true
+    }
+
+    /// Removes an attribute channel, returning false when there was none.
+    pub fn remove_attribute(&mut self, domain: AttributeDomain, name: &str) -> bool {
+        self.attributes.channels.remove(&(domain, name.to_string())).is_some()
+    }
+
+    /// Returns true when the domain has a channel of the given name.
+    pub fn has_attribute(&self, domain: AttributeDomain, name: &str) -> bool {
+        self.attributes.channels.contains_key(&(domain, name.to_string()))
+    }
+
+    /// Returns the attribute channel with the given name, or `None` when
+    /// there is no such channel or its values are not of type `T`.
+    pub fn attribute<T: 'static>(&self, domain: AttributeDomain, name: &str) -> Option<AttributeRef<'_, T, Ix>> {
+        let slots = match domain {
+            AttributeDomain::Vertex => &self.vertex_slots,
+            AttributeDomain::Edge | AttributeDomain::UndirectedEdge => &self.edge_slots,
+            AttributeDomain::Face => &self.face_slots,
+        };
+        self.attributes.channels.get(&(domain, name.to_string()))
+            .and_then(|channel| channel.as_any().downcast_ref())
+            .map(|attribute| AttributeRef { attribute, slots })
+    }
+
+    /// Obtains a mutable reference to the attribute channel with the given
+    /// name, or `None` when there is no such channel or its values are not
+    /// of type `T`.
+    pub fn attribute_mut<T: 'static>(&mut self, domain: AttributeDomain, name: &str) -> Option<AttributeMut<'_, T, Ix>> {
+        let slots = match domain {
+            AttributeDomain::Vertex => &self.vertex_slots,
+            AttributeDomain::Edge | AttributeDomain::UndirectedEdge => &self.edge_slots,
+            AttributeDomain::Face => &self.face_slots,
+        };
+        self.attributes.channels.get_mut(&(domain, name.to_string()))
+            .and_then(|channel| channel.as_any_mut().downcast_mut())
+            .map(|attribute| AttributeMut { attribute, slots })
+    }
+
+    /// Sets the value of an undirected edge attribute for an edge and its twin.
+    pub fn set_undirected_edge_attribute<T: Clone + 'static>(&mut self, name: &str, index: EdgeIndex<Ix>, value: T) -> Result<(), Error<Ix>> {
+        self.check_edge_index(index)?;
+        let twin_index = self.edge(index).twin_index;
+        let mut channel = self.attribute_mut::<T>(AttributeDomain::UndirectedEdge, name)
+            .ok_or(Error::UnknownAttribute)?;
+        if let Some(target) = channel.get_mut(twin_index) {
+            if twin_index.is_valid() {
+                *target = value.clone();
+            }
+        }
+        let target = channel.get_mut(index).ok_or(Error::InvalidEdgeIndex(index))?;
+        *target = value;
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//!
+//! Incremental mesh construction with automatic twin matching.
+//!
+
+use std::collections::HashMap;
+
+use super::*;
+
+
+/// Builds a mesh one polygon at a time, linking twin edges as it goes.
+///
+/// `Mesh::add_polygon` never sets `twin_index`, so a mesh built from plain
+/// index buffers would consist entirely of boundary edges. The builder
+/// remembers every edge by the pair of vertices it connects and, whenever
+/// a new edge runs opposite to an existing one, marks the two as twins.
+///
+/// ```
+/// use hedge::{MeshBuilder, Vertex};
+/// let mut builder = MeshBuilder::new();
+///
+/// let v1 = builder.add_vertex(Vertex::default());
+/// let v2 = builder.add_vertex(Vertex::default());
+/// let v3 = builder.add_vertex(Vertex::default());
+/// let v4 = builder.add_vertex(Vertex::default());
+///
+/// let f1 = builder.add_polygon(&[v1, v2, v3]).unwrap();
+/// let f2 = builder.add_polygon(&[v2, v1, v4]).unwrap();
+///
+/// let mesh = builder.build();
+/// assert!(mesh.face_fn(f1).edge().twin().face().index == f2);
+/// ```
+#[derive(Debug, Default)]
+pub struct MeshBuilder<V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: Mesh<V, E, F, Ix>,
+    edge_map: HashMap<(VertexIndex<Ix>, VertexIndex<Ix>), EdgeIndex<Ix>>
+}
+
+impl MeshBuilder {
+    /// Creates a builder for a mesh with `usize` indices; builders for
+    /// another `IndexType` are created with `MeshBuilder::default`.
+    pub fn new() -> MeshBuilder {
+        MeshBuilder::default()
+    }
+}
+
+impl<V, E, F, Ix: IndexType> MeshBuilder<V, E, F, Ix> {
+    /// Continues building on an existing mesh.
+    ///
+    /// Every connected edge of the mesh is registered so that new polygons
+    /// are matched against them as well.
+    pub fn from_mesh(mesh: Mesh<V, E, F, Ix>) -> MeshBuilder<V, E, F, Ix> {
+        let mut edge_map = HashMap::new();
+        for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+            if edge.next_index.is_valid() {
+                let to = mesh.edge(edge.next_index).vertex_index;
+                edge_map.insert((edge.vertex_index, to), mesh.edge_index_at(i));
+            }
+        }
+        MeshBuilder {
+            mesh,
+            edge_map
+        }
+    }
+
+    /// Adds the provided `Vertex` to the mesh and returns it's `VertexIndex`
+    ///
+    /// Panics when the mesh can't take another vertex; see `try_add_vertex`.
+    pub fn add_vertex(&mut self, vert: Vertex<V, Ix>) -> VertexIndex<Ix> {
+        self.mesh.add_vertex(vert)
+    }
+
+    /// Adds a new vertex at the given position and returns it's `VertexIndex`
+    ///
+    /// Panics when the mesh can't take another vertex; see
+    /// `try_add_vertex_at`.
+    pub fn add_vertex_at(&mut self, position: Position) -> VertexIndex<Ix>
+        where V: Default
+    {
+        self.mesh.add_vertex_at(position)
+    }
+
+    /// Adds the provided `Vertex` to the mesh, reporting an `Error` when the
+    /// mesh can't take another vertex. See `Mesh::try_add_vertex`.
+    pub fn try_add_vertex(&mut self, vert: Vertex<V, Ix>) -> Result<VertexIndex<Ix>, Error<Ix>> {
+        self.mesh.try_add_vertex(vert)
+    }
+
+    /// Adds a new vertex at the given position, reporting an `Error` when
+    /// the mesh can't take another vertex.
+    pub fn try_add_vertex_at(&mut self, position: Position) -> Result<VertexIndex<Ix>, Error<Ix>>
+        where V: Default
+    {
+        self.mesh.try_add_vertex_at(position)
+    }
+
+    /// Create a new face given a slice of vertex indices, linking each of
+    /// its edges to an existing opposite edge when there is one.
+    /// Returns the index of the newly added `Face`.
+    ///
+    /// Adding an edge which already exists means it would be shared by more
+    /// than two faces (or that the faces disagree on their winding), and is
+    /// reported as `Error::NonManifoldEdge` without modifying the mesh.
+    pub fn add_polygon(&mut self, verts: &[VertexIndex<Ix>]) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        for i in 0 .. verts.len() {
+            let key = (verts[i], verts[(i + 1) % verts.len()]);
+            if let Some(&existing) = self.edge_map.get(&key) {
+                return Err(Error::NonManifoldEdge(existing));
+            }
+        }
+
+        let result = self.mesh.try_add_polygon(verts)?;
+
+        let edge_indices: Vec<EdgeIndex<Ix>> = self.mesh.edges(self.mesh.face(result)).collect();
+        for eindex in edge_indices {
+            let from = self.mesh.edge(eindex).vertex_index;
+            let to = self.mesh.edge_fn(eindex).next().vertex().index;
+            self.edge_map.insert((from, to), eindex);
+            if let Some(&twin) = self.edge_map.get(&(to, from)) {
+                self.mesh.set_twin_edges(eindex, twin);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the mesh built so far.
+    pub fn mesh(&self) -> &Mesh<V, E, F, Ix> {
+        &self.mesh
+    }
+
+    /// Finishes building and returns the mesh.
+    pub fn build(self) -> Mesh<V, E, F, Ix> {
+        self.mesh
+    }
+}
+
+impl<V: Default, E: Default, F: Default, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Creates a new mesh from a list of polygons given as indices into a
+    /// vertex buffer, linking twin edges between adjacent polygons.
+    ///
+    /// Indices are zero based like those of typical index buffers; the
+    /// vertex at index `i` is added to the mesh as `VertexIndex(i + 1)`
+    /// since the first component of each list is reserved.
+    ///
+    /// ```
+    /// let quad: &[usize] = &[0, 1, 2, 3];
+    /// let tri: &[usize] = &[1, 0, 4];
+    /// let mesh: hedge::Mesh = hedge::Mesh::from_indexed_faces(5, &[quad, tri]).unwrap();
+    ///
+    /// assert_eq!(mesh.faces().count(), 2);
+    /// ```
+    pub fn from_indexed_faces(vertex_count: usize, faces: &[&[usize]]) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+        let mut builder = MeshBuilder::default();
+        let vertices = (0 .. vertex_count)
+            .map(|_| builder.try_add_vertex(Vertex::default()))
+            .collect::<Result<Vec<VertexIndex<Ix>>, Error<Ix>>>()?;
+
+        let mut verts = Vec::new();
+        for face in faces {
+            verts.clear();
+            for &index in face.iter() {
+                match vertices.get(index) {
+                    Some(&vindex) => verts.push(vindex),
+                    None if index < <Ix as IndexType>::max().index() =>
+                        return Err(Error::InvalidVertexIndex(builder.mesh().vertex_index_at(index + 1))),
+                    None => return Err(Error::IndexOverflow),
+                }
+            }
+            builder.add_polygon(&verts)?;
+        }
+
+        Ok(builder.build())
+    }
+}
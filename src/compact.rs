@@ -0,0 +1,114 @@
+//!
+//! Packing of component lists left with tombstones.
+//!
+
+use super::*;
+
+
+/// Maps the indices a mesh had before `Mesh::compact` to its indices
+/// afterwards.
+///
+/// Each list is addressed by the offset of the old index and holds the
+/// default index for the tombstones that were dropped.
+#[derive(Debug, Default, Clone)]
+pub struct Remap<Ix: IndexType = usize> {
+    pub vertices: Vec<VertexIndex<Ix>>,
+    pub edges: Vec<EdgeIndex<Ix>>,
+    pub faces: Vec<FaceIndex<Ix>>,
+}
+
+impl<Ix: IndexType> Remap<Ix> {
+    /// Returns the new index of a vertex, or the default index when it had
+    /// been removed.
+    pub fn vertex(&self, index: VertexIndex<Ix>) -> VertexIndex<Ix> {
+        self.vertices.get(index.offset()).cloned().unwrap_or_default()
+    }
+
+    /// Returns the new index of an edge, or the default index when it had
+    /// been removed.
+    pub fn edge(&self, index: EdgeIndex<Ix>) -> EdgeIndex<Ix> {
+        self.edges.get(index.offset()).cloned().unwrap_or_default()
+    }
+
+    /// Returns the new index of a face, or the default index when it had
+    /// been removed.
+    pub fn face(&self, index: FaceIndex<Ix>) -> FaceIndex<Ix> {
+        self.faces.get(index.offset()).cloned().unwrap_or_default()
+    }
+}
+
+/// Keeps the values whose slot survives compaction, given the offsets
+/// returned by `Slots::compact`. The first value always survives.
+pub(crate) fn retain_live<T>(values: &mut Vec<T>, offsets: &[usize]) {
+    let mut offset = 0;
+    values.retain(|_| {
+        let live = offset == 0 || offsets.get(offset) != Some(&INVALID_COMPONENT_INDEX);
+        offset += 1;
+        live
+    });
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Drops every tombstone left by `RemovalMode::Tombstone`, packing the
+    /// component lists and their attribute channels while keeping the order
+    /// of the remaining components.
+    ///
+    /// Components which change slots get new indices, which are returned in
+    /// a `Remap` since all earlier indices to them become stale.
+    ///
+    /// ```
+    /// use hedge::{Mesh, RemovalMode, RemovalPolicy, Validation, Vertex};
+    /// let mut mesh = Mesh::new();
+    /// mesh.set_removal_mode(RemovalMode::Tombstone);
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// mesh.remove_vertex(v1, RemovalPolicy::Refuse).unwrap();
+    ///
+    /// let remap = mesh.compact();
+    /// assert!(!remap.vertex(v1).is_valid());
+    /// assert!(mesh.is_current_vertex(remap.vertex(v2)));
+    /// assert!(!mesh.is_current_vertex(v2));
+    /// ```
+    pub fn compact(&mut self) -> Remap<Ix> {
+        let vertex_offsets = self.vertex_slots.compact(self.vertex_list.len());
+        let edge_offsets = self.edge_slots.compact(self.edge_list.len());
+        let face_offsets = self.face_slots.compact(self.face_list.len());
+
+        retain_live(&mut self.vertex_list, &vertex_offsets);
+        retain_live(&mut self.edge_list, &edge_offsets);
+        retain_live(&mut self.face_list, &face_offsets);
+        self.attributes.compact(AttributeDomain::Vertex, &vertex_offsets);
+        self.attributes.compact(AttributeDomain::Edge, &edge_offsets);
+        self.attributes.compact(AttributeDomain::UndirectedEdge, &edge_offsets);
+        self.attributes.compact(AttributeDomain::Face, &face_offsets);
+
+        let remap = Remap {
+            vertices: vertex_offsets.iter()
+                .map(|&offset| self.vertex_index_at(offset))
+                .collect(),
+            edges: edge_offsets.iter()
+                .map(|&offset| self.edge_index_at(offset))
+                .collect(),
+            faces: face_offsets.iter()
+                .map(|&offset| self.face_index_at(offset))
+                .collect(),
+        };
+
+        for vertex in &mut self.vertex_list {
+            vertex.edge_index = remap.edge(vertex.edge_index);
+        }
+        for edge in &mut self.edge_list {
+            edge.twin_index = remap.edge(edge.twin_index);
+            edge.next_index = remap.edge(edge.next_index);
+            edge.prev_index = remap.edge(edge.prev_index);
+            edge.face_index = remap.face(edge.face_index);
+            edge.vertex_index = remap.vertex(edge.vertex_index);
+        }
+        for face in &mut self.face_list {
+            face.edge_index = remap.edge(face.edge_index);
+        }
+
+        remap
+    }
+}
@@ -0,0 +1,312 @@
+//!
+//! Mutable function sets.
+//!
+//! `FaceFnMut`, `VertexFnMut` and `EdgeFnMut` navigate the mesh like their
+//! read-only counterparts while holding it mutably, so that payloads,
+//! positions and attributes can be edited and local operators applied
+//! along the way.
+//!
+//! A function set only exists for a current index: `Mesh::face_fn_mut` and
+//! friends return `None` for stale or default indices, and so does every
+//! navigation step that would lead to one, such as the face of a boundary
+//! edge.
+//!
+
+use super::*;
+
+
+/// Mutable function set for operations related to the Face struct
+#[derive(Debug)]
+pub struct FaceFnMut<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh mut Mesh<V, E, F, Ix>,
+    pub index: FaceIndex<Ix>
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> FaceFnMut<'mesh, V, E, F, Ix> {
+    pub(crate) fn new(index: FaceIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> FaceFnMut<'mesh, V, E, F, Ix> {
+        FaceFnMut {
+            mesh,
+            index
+        }
+    }
+
+    /// Creates a function set for `index`, or `None` when the index is
+    /// stale or refers to the default face.
+    fn checked(index: FaceIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> Option<FaceFnMut<'mesh, V, E, F, Ix>> {
+        mesh.get_face(index)?;
+        Some(FaceFnMut::new(index, mesh))
+    }
+
+    /// Returns a read-only `FaceFn` for this face.
+    pub fn as_fn(&self) -> FaceFn<'_, V, E, F, Ix> {
+        self.mesh.face_fn(self.index)
+    }
+
+    /// Convert this `FaceFnMut` to an `EdgeFnMut`.
+    pub fn edge(self) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.face(self.index).edge_index;
+        EdgeFnMut::checked(index, self.mesh)
+    }
+
+    /// Returns the user data of this face.
+    pub fn data(&self) -> &F {
+        &self.mesh.face_list[self.index.offset()].data
+    }
+
+    /// Replaces the user data of this face.
+    pub fn set_data(&mut self, data: F) -> Result<(), Error<Ix>> {
+        self.mesh.check_face_index(self.index)?;
+        self.mesh.face_list[self.index.offset()].data = data;
+        Ok(())
+    }
+
+    /// Sets the value of this face in the face attribute channel `name`.
+    pub fn set_attribute<T: 'static>(&mut self, name: &str, value: T) -> Result<(), Error<Ix>> {
+        self.mesh.check_face_index(self.index)?;
+        let mut channel = self.mesh.attribute_mut::<T>(AttributeDomain::Face, name)
+            .ok_or(Error::UnknownAttribute)?;
+        channel[self.index] = value;
+        Ok(())
+    }
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> Validation for FaceFnMut<'mesh, V, E, F, Ix> {
+    fn is_valid(&self) -> bool {
+        self.mesh.face(self.index).is_valid()
+    }
+}
+
+/// Mutable function set for operations related to the Vertex struct
+#[derive(Debug)]
+pub struct VertexFnMut<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh mut Mesh<V, E, F, Ix>,
+    pub index: VertexIndex<Ix>
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> VertexFnMut<'mesh, V, E, F, Ix> {
+    pub(crate) fn new(index: VertexIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> VertexFnMut<'mesh, V, E, F, Ix> {
+        VertexFnMut {
+            mesh,
+            index
+        }
+    }
+
+    /// Creates a function set for `index`, or `None` when the index is
+    /// stale or refers to the default vertex.
+    fn checked(index: VertexIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> Option<VertexFnMut<'mesh, V, E, F, Ix>> {
+        mesh.get_vertex(index)?;
+        Some(VertexFnMut::new(index, mesh))
+    }
+
+    /// Returns a read-only `VertexFn` for this vertex.
+    pub fn as_fn(&self) -> VertexFn<'_, V, E, F, Ix> {
+        self.mesh.vertex_fn(self.index)
+    }
+
+    /// Convert this `VertexFnMut` to an `EdgeFnMut` of its outgoing edge.
+    pub fn edge(self) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.vertex(self.index).edge_index;
+        EdgeFnMut::checked(index, self.mesh)
+    }
+
+    /// Returns the user data of this vertex.
+    pub fn data(&self) -> &V {
+        &self.mesh.vertex_list[self.index.offset()].data
+    }
+
+    /// Replaces the user data of this vertex.
+    pub fn set_data(&mut self, data: V) -> Result<(), Error<Ix>> {
+        self.mesh.check_vertex_index(self.index)?;
+        self.mesh.vertex_list[self.index.offset()].data = data;
+        Ok(())
+    }
+
+    /// Returns the position of this vertex.
+    pub fn position(&self) -> &Position {
+        self.mesh.position(self.index)
+    }
+
+    /// Moves this vertex to `position`.
+    pub fn set_position(&mut self, position: Position) -> Result<(), Error<Ix>> {
+        self.mesh.try_set_position(self.index, position)
+    }
+
+    /// Sets the value of this vertex in the vertex attribute channel `name`.
+    pub fn set_attribute<T: 'static>(&mut self, name: &str, value: T) -> Result<(), Error<Ix>> {
+        self.mesh.check_vertex_index(self.index)?;
+        let mut channel = self.mesh.attribute_mut::<T>(AttributeDomain::Vertex, name)
+            .ok_or(Error::UnknownAttribute)?;
+        channel[self.index] = value;
+        Ok(())
+    }
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> Validation for VertexFnMut<'mesh, V, E, F, Ix> {
+    fn is_valid(&self) -> bool {
+        self.mesh.vertex(self.index).is_valid()
+    }
+}
+
+/// Mutable function set for operations related to the Edge struct
+#[derive(Debug)]
+pub struct EdgeFnMut<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh mut Mesh<V, E, F, Ix>,
+    pub index: EdgeIndex<Ix>
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> EdgeFnMut<'mesh, V, E, F, Ix> {
+    pub(crate) fn new(index: EdgeIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> EdgeFnMut<'mesh, V, E, F, Ix> {
+        EdgeFnMut {
+            mesh,
+            index
+        }
+    }
+
+    /// Creates a function set for `index`, or `None` when the index is
+    /// stale or refers to the default edge.
+    fn checked(index: EdgeIndex<Ix>, mesh: &'mesh mut Mesh<V, E, F, Ix>) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        mesh.get_edge(index)?;
+        Some(EdgeFnMut::new(index, mesh))
+    }
+
+    /// Returns a read-only `EdgeFn` for this edge.
+    pub fn as_fn(&self) -> EdgeFn<'_, V, E, F, Ix> {
+        self.mesh.edge_fn(self.index)
+    }
+
+    /// Convert this `EdgeFnMut` to an `EdgeFnMut` of it's next edge
+    pub fn next(self) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.edge(self.index).next_index;
+        EdgeFnMut::checked(index, self.mesh)
+    }
+
+    /// Convert this `EdgeFnMut` to an `EdgeFnMut` of it's prev edge
+    pub fn prev(self) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.edge(self.index).prev_index;
+        EdgeFnMut::checked(index, self.mesh)
+    }
+
+    /// Convert this `EdgeFnMut` to an `EdgeFnMut` of it's twin edge
+    pub fn twin(self) -> Option<EdgeFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.edge(self.index).twin_index;
+        EdgeFnMut::checked(index, self.mesh)
+    }
+
+    /// Convert this `EdgeFnMut` to a `FaceFnMut`
+    pub fn face(self) -> Option<FaceFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.edge(self.index).face_index;
+        FaceFnMut::checked(index, self.mesh)
+    }
+
+    /// Convert this `EdgeFnMut` to a `VertexFnMut`
+    pub fn vertex(self) -> Option<VertexFnMut<'mesh, V, E, F, Ix>> {
+        let index = self.mesh.edge(self.index).vertex_index;
+        VertexFnMut::checked(index, self.mesh)
+    }
+
+    /// Returns the user data of this edge.
+    pub fn data(&self) -> &E {
+        &self.mesh.edge_list[self.index.offset()].data
+    }
+
+    /// Replaces the user data of this edge.
+    pub fn set_data(&mut self, data: E) -> Result<(), Error<Ix>> {
+        self.mesh.check_edge_index(self.index)?;
+        self.mesh.edge_list[self.index.offset()].data = data;
+        Ok(())
+    }
+
+    /// Sets the value of this edge in the edge attribute channel `name`.
+    pub fn set_attribute<T: 'static>(&mut self, name: &str, value: T) -> Result<(), Error<Ix>> {
+        self.mesh.check_edge_index(self.index)?;
+        let mut channel = self.mesh.attribute_mut::<T>(AttributeDomain::Edge, name)
+            .ok_or(Error::UnknownAttribute)?;
+        channel[self.index] = value;
+        Ok(())
+    }
+
+    /// Sets the value of this edge and its twin in the undirected edge
+    /// attribute channel `name`.
+    pub fn set_undirected_attribute<T: Clone + 'static>(&mut self, name: &str, value: T) -> Result<(), Error<Ix>> {
+        self.mesh.set_undirected_edge_attribute(name, self.index, value)
+    }
+
+    /// Flips this edge within the two triangles it separates; see
+    /// `Mesh::flip_edge`. The function set stays on the flipped edge.
+    pub fn flip(&mut self) -> Result<(), Error<Ix>> {
+        self.mesh.flip_edge(self.index)
+    }
+
+    /// Splits this edge at `t`, returning a function set for the new
+    /// vertex; see `Mesh::split_edge`.
+    pub fn split(self, t: f32) -> Result<VertexFnMut<'mesh, V, E, F, Ix>, Error<Ix>>
+        where V: Default, E: Default
+    {
+        let index = self.mesh.split_edge(self.index, t)?;
+        Ok(VertexFnMut::new(index, self.mesh))
+    }
+
+    /// Collapses this edge into a vertex at `target_position`, returning a
+    /// function set for the merged vertex; see `Mesh::collapse_edge`.
+    pub fn collapse(self, target_position: Position) -> Result<VertexFnMut<'mesh, V, E, F, Ix>, Error<Ix>>
+        where V: Default, E: Default, F: Default
+    {
+        let index = self.mesh.collapse_edge(self.index, target_position)?;
+        Ok(VertexFnMut::new(index, self.mesh))
+    }
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> Validation for EdgeFnMut<'mesh, V, E, F, Ix> {
+    fn is_valid(&self) -> bool {
+        self.mesh.edge(self.index).is_valid()
+    }
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Returns a `FaceFnMut` for the given index, or `None` when the index
+    /// is stale or refers to the default face.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Vertex};
+    /// let mut mesh: Mesh<(), (), &str> = Mesh::default();
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// let v3 = mesh.add_vertex(Vertex::default());
+    /// let f1 = mesh.add_triangle(v1, v2, v3);
+    ///
+    /// mesh.face_fn_mut(f1).unwrap().set_data("roof").unwrap();
+    /// assert_eq!(*mesh.face_fn(f1).data(), "roof");
+    /// ```
+    pub fn face_fn_mut(&mut self, index: FaceIndex<Ix>) -> Option<FaceFnMut<'_, V, E, F, Ix>> {
+        FaceFnMut::checked(index, self)
+    }
+
+    /// Returns an `EdgeFnMut` for the given index, or `None` when the index
+    /// is stale or refers to the default edge.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Position};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex_at(Position::new(2.0, 0.0, 0.0));
+    /// let v3 = mesh.add_vertex_at(Position::new(0.0, 2.0, 0.0));
+    /// let f1 = mesh.add_triangle(v1, v2, v3);
+    ///
+    /// let edge = mesh.find_edge(v2, v3).unwrap();
+    /// let mut middle = mesh.edge_fn_mut(edge).unwrap().split(0.5).unwrap();
+    /// middle.set_position(Position::new(2.0, 2.0, 0.0)).unwrap();
+    ///
+    /// assert_eq!(mesh.vertices(mesh.face(f1)).count(), 4);
+    /// ```
+    pub fn edge_fn_mut(&mut self, index: EdgeIndex<Ix>) -> Option<EdgeFnMut<'_, V, E, F, Ix>> {
+        EdgeFnMut::checked(index, self)
+    }
+
+    /// Returns a `VertexFnMut` for the given index, or `None` when the index
+    /// is stale or refers to the default vertex.
+    pub fn vertex_fn_mut(&mut self, index: VertexIndex<Ix>) -> Option<VertexFnMut<'_, V, E, F, Ix>> {
+        VertexFnMut::checked(index, self)
+    }
+}
@@ -0,0 +1,192 @@
+//!
+//! Whole mesh integrity checks.
+//!
+//! Where the `Validation` trait only looks at a single component, the checks
+//! here walk every list in the mesh and verify the half-edge invariants that
+//! tie the components together.
+//!
+
+use super::*;
+
+
+/// Identifies a single component of the mesh.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Component<Ix: IndexType = usize> {
+    Vertex(VertexIndex<Ix>),
+    Edge(EdgeIndex<Ix>),
+    Face(FaceIndex<Ix>),
+}
+
+/// A violated half-edge invariant.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Violation<Ix: IndexType = usize> {
+    /// The component holds an index beyond the end of the referenced list.
+    OutOfBounds(Component<Ix>),
+    /// The component holds an index to a component which has since been
+    /// removed or moved to another slot.
+    StaleIndex(Component<Ix>),
+    /// The edge of this vertex doesn't originate at it, or the vertex has
+    /// outgoing edges but no edge of its own.
+    VertexEdgeMismatch(VertexIndex<Ix>),
+    /// The edge doesn't originate at any vertex.
+    MissingVertex(EdgeIndex<Ix>),
+    /// The edge is missing its next or previous edge.
+    DisconnectedEdge(EdgeIndex<Ix>),
+    /// `edge->next->prev` is not this edge.
+    NextPrevMismatch(EdgeIndex<Ix>),
+    /// `edge->prev->next` is not this edge.
+    PrevNextMismatch(EdgeIndex<Ix>),
+    /// `edge->twin->twin` is not this edge.
+    TwinMismatch(EdgeIndex<Ix>),
+    /// The twin of this edge doesn't originate where this edge ends.
+    TwinVertexMismatch(EdgeIndex<Ix>),
+    /// The next edge in the loop belongs to a different face.
+    FaceMismatch(EdgeIndex<Ix>),
+    /// The root edge of this face belongs to a different face.
+    FaceEdgeMismatch(FaceIndex<Ix>),
+    /// Walking the loop of this face never returns to its root edge.
+    OpenFaceLoop(FaceIndex<Ix>),
+}
+
+/// The result of `Mesh::check_integrity`.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct IntegrityReport<Ix: IndexType = usize> {
+    /// Every violation found, in the order vertices, edges and faces
+    /// were visited.
+    pub violations: Vec<Violation<Ix>>,
+}
+
+impl<Ix: IndexType> Validation for IntegrityReport<Ix> {
+    /// A report is "valid" when no violations were found.
+    fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Walks all vertices, edges and faces of the mesh and reports every
+    /// violated half-edge invariant.
+    ///
+    /// Boundary edges (those without a twin) and edges without a face are
+    /// allowed, but every edge must be part of a consistent edge loop.
+    /// Tombstones are skipped, but live components must not refer to them.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Vertex, Validation};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// let v3 = mesh.add_vertex(Vertex::default());
+    /// mesh.add_triangle(v1, v2, v3);
+    ///
+    /// assert!(mesh.check_integrity().is_valid());
+    /// ```
+    pub fn check_integrity(&self) -> IntegrityReport<Ix> {
+        let mut violations = Vec::new();
+        let vertex_count = self.vertex_list.len();
+        let edge_count = self.edge_list.len();
+        let face_count = self.face_list.len();
+
+        let mut has_outgoing = vec![false; vertex_count];
+        for edge in self.edge_list.iter().skip(1) {
+            if edge.vertex_index.offset() < vertex_count {
+                has_outgoing[edge.vertex_index.offset()] = true;
+            }
+        }
+
+        for (i, vertex) in self.vertex_list.iter().enumerate().skip(1) {
+            let index = self.vertex_index_at(i);
+            if !self.is_current_vertex(index) {
+                continue;
+            }
+            if vertex.edge_index.offset() >= edge_count {
+                violations.push(Violation::OutOfBounds(Component::Vertex(index)));
+            } else if !self.is_current_edge(vertex.edge_index) {
+                violations.push(Violation::StaleIndex(Component::Vertex(index)));
+            } else if vertex.edge_index.is_valid() {
+                if self.edge(vertex.edge_index).vertex_index != index {
+                    violations.push(Violation::VertexEdgeMismatch(index));
+                }
+            } else if has_outgoing[i] {
+                violations.push(Violation::VertexEdgeMismatch(index));
+            }
+        }
+
+        for (i, edge) in self.edge_list.iter().enumerate().skip(1) {
+            let index = self.edge_index_at(i);
+            if !self.is_current_edge(index) {
+                continue;
+            }
+            let out_of_bounds = edge.twin_index.offset() >= edge_count ||
+                edge.next_index.offset() >= edge_count ||
+                edge.prev_index.offset() >= edge_count ||
+                edge.face_index.offset() >= face_count ||
+                edge.vertex_index.offset() >= vertex_count;
+            if out_of_bounds {
+                violations.push(Violation::OutOfBounds(Component::Edge(index)));
+                continue;
+            }
+            let stale = !self.is_current_edge(edge.twin_index) ||
+                !self.is_current_edge(edge.next_index) ||
+                !self.is_current_edge(edge.prev_index) ||
+                !self.is_current_face(edge.face_index) ||
+                !self.is_current_vertex(edge.vertex_index);
+            if stale {
+                violations.push(Violation::StaleIndex(Component::Edge(index)));
+                continue;
+            }
+
+            if !edge.vertex_index.is_valid() {
+                violations.push(Violation::MissingVertex(index));
+            }
+            if !edge.is_connected() {
+                violations.push(Violation::DisconnectedEdge(index));
+            }
+            if edge.next_index.is_valid() {
+                let next = self.edge(edge.next_index);
+                if next.prev_index != index {
+                    violations.push(Violation::NextPrevMismatch(index));
+                }
+                if next.face_index != edge.face_index {
+                    violations.push(Violation::FaceMismatch(index));
+                }
+            }
+            if edge.prev_index.is_valid() && self.edge(edge.prev_index).next_index != index {
+                violations.push(Violation::PrevNextMismatch(index));
+            }
+            if edge.twin_index.is_valid() {
+                let twin = self.edge(edge.twin_index);
+                if twin.twin_index != index {
+                    violations.push(Violation::TwinMismatch(index));
+                } else if edge.next_index.is_valid() &&
+                    twin.vertex_index != self.edge(edge.next_index).vertex_index {
+                        violations.push(Violation::TwinVertexMismatch(index));
+                    }
+            }
+        }
+
+        for (i, face) in self.face_list.iter().enumerate().skip(1) {
+            let index = self.face_index_at(i);
+            if !self.is_current_face(index) {
+                continue;
+            }
+            if face.edge_index.offset() >= edge_count {
+                violations.push(Violation::OutOfBounds(Component::Face(index)));
+                continue;
+            }
+            if !self.is_current_edge(face.edge_index) {
+                violations.push(Violation::StaleIndex(Component::Face(index)));
+                continue;
+            }
+            if self.edge(face.edge_index).face_index != index {
+                violations.push(Violation::FaceEdgeMismatch(index));
+            }
+            if !self.loop_is_closed(face.edge_index) {
+                violations.push(Violation::OpenFaceLoop(index));
+            }
+        }
+
+        IntegrityReport { violations }
+    }
+}
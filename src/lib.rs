@@ -3,11 +3,39 @@
 //! An index based half-edge mesh implementation.
 //!
 
-// TODO: Result types for error handling?
-
 extern crate cgmath;
 
+use cgmath::Point3;
+
+use std::cmp;
+use std::convert::TryFrom;
+use std::error;
 use std::fmt;
+use std::hash::Hash;
+use std::mem;
+
+mod attributes;
+mod builder;
+mod compact;
+mod cursor;
+mod integrity;
+mod normals;
+mod operators;
+mod triangulate;
+#[cfg(feature = "obj")]
+pub mod obj;
+pub mod simplify;
+pub mod smooth;
+pub mod subdivide;
+
+pub use attributes::{Attribute, AttributeDomain, AttributeMut, AttributeRef, ComponentIndex, Interpolation};
+pub use attributes::{linear_interpolation, nearest_interpolation};
+pub use builder::MeshBuilder;
+pub use compact::Remap;
+pub use cursor::{EdgeFnMut, FaceFnMut, VertexFnMut};
+pub use integrity::{Component, IntegrityReport, Violation};
+pub use normals::{Normal, NormalWeighting, VERTEX_NORMAL_ATTRIBUTE};
+pub use triangulate::Triangulation;
 
 
 /// An interface for asserting the validity of components in the mesh.
@@ -17,59 +45,376 @@ pub trait Validation {
 }
 
 
+/// The integer type stored in the indices of a `Mesh`.
+///
+/// Narrower types shrink every component, most notably `Edge` which holds
+/// five indices, but limit the number of components in each list to what
+/// `IndexType::max` can address. `usize` is used by default.
+///
+/// The generations carried by indices are stored in the same type and wrap
+/// around after `IndexType::max` removals from the same slot. An index kept
+/// across that many removals is no longer recognized as stale, which is
+/// only a practical concern for `u16` meshes.
+///
+/// ```
+/// use hedge::{Mesh, Vertex};
+/// let mut mesh = Mesh::<(), (), (), u32>::default();
+///
+/// let v1 = mesh.add_vertex(Vertex::default());
+/// let v2 = mesh.add_vertex(Vertex::default());
+/// let v3 = mesh.add_vertex(Vertex::default());
+/// let f1 = mesh.add_triangle(v1, v2, v3);
+///
+/// assert!(mesh.face_fn(f1).edge().vertex().index == v1);
+/// ```
+pub trait IndexType: Copy + Default + Hash + Ord + fmt::Debug + 'static {
+    /// Converts an offset into a component list.
+    ///
+    /// Panics when the offset doesn't fit, which the methods adding
+    /// components rule out beforehand.
+    fn new(offset: usize) -> Self;
+    /// The offset into a component list.
+    fn index(&self) -> usize;
+    /// The largest representable offset.
+    fn max() -> Self;
+}
+
+impl IndexType for usize {
+    fn new(offset: usize) -> usize {
+        offset
+    }
+
+    fn index(&self) -> usize {
+        *self
+    }
+
+    fn max() -> usize {
+        usize::MAX
+    }
+}
+
+impl IndexType for u32 {
+    fn new(offset: usize) -> u32 {
+        u32::try_from(offset).expect("offset exceeds the range of u32 indices")
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn max() -> u32 {
+        u32::MAX
+    }
+}
+
+impl IndexType for u16 {
+    fn new(offset: usize) -> u16 {
+        u16::try_from(offset).expect("offset exceeds the range of u16 indices")
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn max() -> u16 {
+        u16::MAX
+    }
+}
+
+
 /// Our default value for uninitialized or unconnected components in the mesh.
 pub const INVALID_COMPONENT_INDEX: usize = 0;
 
 /// Type alias for indices into vertex attribute storage
 pub type VertexAttributeIndex = usize;
 
-#[derive(Default, Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct VertexIndex(usize);
+/// The type of vertex positions stored by a `Mesh`.
+pub type Position = Point3<f32>;
+
+/// Bookkeeping for every slot of a component list.
+///
+/// A slot's generation is bumped whenever it stops holding the component it
+/// held, either because the component was removed or because it was moved
+/// to another slot. Indices carry the generation of their slot at the time
+/// they were handed out, so that indices to components which have since
+/// moved or been removed are recognized as stale.
+///
+/// Slots of components removed with `RemovalMode::Tombstone` are marked as
+/// dead until `Mesh::compact` packs the list.
+///
+/// Generations are stored in the index type of the mesh, so that narrow
+/// indices stay small, and wrap around once they exceed its range.
+#[derive(Debug, Default)]
+struct Slots<Ix: IndexType> {
+    generations: Vec<Ix>,
+    dead: Vec<bool>,
+    dead_count: usize,
+}
+
+impl<Ix: IndexType> Slots<Ix> {
+    fn generation(&self, offset: usize) -> Ix {
+        self.generations.get(offset).cloned().unwrap_or_default()
+    }
+
+    fn is_dead(&self, offset: usize) -> bool {
+        self.dead.get(offset).cloned().unwrap_or(false)
+    }
+
+    fn retire(&mut self, offset: usize) {
+        if offset >= self.generations.len() {
+            self.generations.resize(offset + 1, Ix::default());
+        }
+        let generation = self.generations[offset];
+        self.generations[offset] = if generation == <Ix as IndexType>::max() {
+            Ix::default()
+        } else {
+            Ix::new(generation.index() + 1)
+        };
+    }
+
+    fn set_dead(&mut self, offset: usize, dead: bool) {
+        if self.is_dead(offset) == dead {
+            return;
+        }
+        if offset >= self.dead.len() {
+            self.dead.resize(offset + 1, false);
+        }
+        self.dead[offset] = dead;
+        if dead {
+            self.dead_count += 1;
+        } else {
+            self.dead_count -= 1;
+        }
+    }
 
-impl Validation for VertexIndex {
+    /// Records a `swap_remove` of `offset` from a list whose last slot was
+    /// `last`.
+    fn swap_remove(&mut self, offset: usize, last: usize) {
+        debug_assert!(!self.is_dead(offset));
+        self.retire(offset);
+        if offset != last {
+            self.retire(last);
+        }
+        let moved_dead = offset != last && self.is_dead(last);
+        self.set_dead(last, false);
+        self.set_dead(offset, moved_dead);
+    }
+
+    /// Marks the slot as holding a tombstone.
+    fn bury(&mut self, offset: usize) {
+        self.retire(offset);
+        self.set_dead(offset, true);
+    }
+
+    /// Removes the tombstones from a list of `len` slots, returning the new
+    /// offset of every slot, or `INVALID_COMPONENT_INDEX` for tombstones.
+    fn compact(&mut self, len: usize) -> Vec<usize> {
+        let mut result = vec![INVALID_COMPONENT_INDEX; len];
+        let mut next = 0;
+        for (offset, target) in result.iter_mut().enumerate() {
+            if !self.is_dead(offset) {
+                *target = next;
+                if next != offset {
+                    self.retire(next);
+                }
+                next += 1;
+            }
+        }
+        for offset in next .. len {
+            self.retire(offset);
+        }
+        self.dead.clear();
+        self.dead_count = 0;
+        result
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Hash, PartialOrd, Clone, Copy)]
+pub struct VertexIndex<Ix: IndexType = usize>(Ix, Ix);
+
+impl<Ix: IndexType> Validation for VertexIndex<Ix> {
     fn is_valid(&self) -> bool {
-        self.0 != INVALID_COMPONENT_INDEX
+        self.0.index() != INVALID_COMPONENT_INDEX
     }
 }
 
-#[derive(Default, Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct EdgeIndex(usize);
+#[derive(Default, Debug, PartialEq, Eq, Hash, PartialOrd, Clone, Copy)]
+pub struct EdgeIndex<Ix: IndexType = usize>(Ix, Ix);
 
-impl Validation for EdgeIndex {
+impl<Ix: IndexType> Validation for EdgeIndex<Ix> {
     fn is_valid(&self) -> bool {
-        self.0 != INVALID_COMPONENT_INDEX
+        self.0.index() != INVALID_COMPONENT_INDEX
     }
 }
 
-#[derive(Default, Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct FaceIndex(usize);
+#[derive(Default, Debug, PartialEq, Eq, Hash, PartialOrd, Clone, Copy)]
+pub struct FaceIndex<Ix: IndexType = usize>(Ix, Ix);
 
-impl Validation for FaceIndex {
+impl<Ix: IndexType> Validation for FaceIndex<Ix> {
     fn is_valid(&self) -> bool {
-        self.0 != INVALID_COMPONENT_INDEX
+        self.0.index() != INVALID_COMPONENT_INDEX
     }
 }
 
 
+/// Errors reported by fallible mesh operations.
+///
+/// Most mutating methods of `Mesh` only check their input with debug
+/// assertions. Their `try_*` counterparts perform the same checks in every
+/// build and report failures with this type instead.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error<Ix: IndexType = usize> {
+    /// The vertex index is the default index or out of bounds.
+    InvalidVertexIndex(VertexIndex<Ix>),
+    /// The edge index is the default index or out of bounds.
+    InvalidEdgeIndex(EdgeIndex<Ix>),
+    /// The face index is the default index or out of bounds.
+    InvalidFaceIndex(FaceIndex<Ix>),
+    /// The vertex still has edges connected to it.
+    VertexInUse(VertexIndex<Ix>),
+    /// The edge already has a twin, so adding another face to it would
+    /// produce a non-manifold edge.
+    NonManifoldEdge(EdgeIndex<Ix>),
+    /// The edge already has a different twin which would be left pointing
+    /// at an edge that no longer points back.
+    DanglingTwin(EdgeIndex<Ix>),
+    /// The edges don't run between the same pair of vertices in opposite
+    /// directions.
+    MismatchedTwins(EdgeIndex<Ix>, EdgeIndex<Ix>),
+    /// The second edge doesn't start where the first one ends.
+    DisjointEdges(EdgeIndex<Ix>, EdgeIndex<Ix>),
+    /// The edge is missing a next or previous edge required by the operation.
+    DisconnectedEdge(EdgeIndex<Ix>),
+    /// The edge is already connected where the operation expected a free end.
+    EdgeAlreadyConnected(EdgeIndex<Ix>),
+    /// Following the next edges starting at this edge never returns to it.
+    OpenEdgeLoop(EdgeIndex<Ix>),
+    /// A polygon needs at least three distinct vertices.
+    DegeneratePolygon,
+    /// The attribute index is out of bounds.
+    InvalidAttributeIndex(VertexAttributeIndex),
+    /// There is no attribute channel of the given name and type.
+    UnknownAttribute,
+    /// The operation requires an interior edge but the edge has no twin.
+    BoundaryEdge(EdgeIndex<Ix>),
+    /// The operation requires a triangle but the face has a different
+    /// number of edges.
+    NonTriangularFace(FaceIndex<Ix>),
+    /// The operation would add a second edge between the two vertices.
+    DuplicateEdge(VertexIndex<Ix>, VertexIndex<Ix>),
+    /// Collapsing the edge would make the mesh non-manifold.
+    LinkCondition(EdgeIndex<Ix>),
+    /// The edge has the same face on both sides.
+    BridgeEdge(EdgeIndex<Ix>),
+    /// The vertex isn't connected to any edges.
+    IsolatedVertex(VertexIndex<Ix>),
+    /// The vertex isn't part of the edge loop of the face.
+    VertexNotInFace(VertexIndex<Ix>, FaceIndex<Ix>),
+    /// The index type can't address any more components.
+    IndexOverflow,
+}
+
+impl<Ix: IndexType> fmt::Display for Error<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidVertexIndex(index) =>
+                write!(f, "invalid vertex index {}", index.offset()),
+            Error::InvalidEdgeIndex(index) =>
+                write!(f, "invalid edge index {}", index.offset()),
+            Error::InvalidFaceIndex(index) =>
+                write!(f, "invalid face index {}", index.offset()),
+            Error::VertexInUse(index) =>
+                write!(f, "vertex {} still has connected edges", index.offset()),
+            Error::NonManifoldEdge(index) =>
+                write!(f, "edge {} already has a twin", index.offset()),
+            Error::DanglingTwin(index) =>
+                write!(f, "edge {} would leave its current twin dangling", index.offset()),
+            Error::MismatchedTwins(a, b) =>
+                write!(f, "edges {} and {} are not opposite each other", a.offset(), b.offset()),
+            Error::DisjointEdges(a, b) =>
+                write!(f, "edge {} does not start where edge {} ends", b.offset(), a.offset()),
+            Error::DisconnectedEdge(index) =>
+                write!(f, "edge {} is not part of an edge loop", index.offset()),
+            Error::EdgeAlreadyConnected(index) =>
+                write!(f, "edge {} is already connected", index.offset()),
+            Error::OpenEdgeLoop(index) =>
+                write!(f, "the edge loop starting at edge {} is not closed", index.offset()),
+            Error::DegeneratePolygon =>
+                write!(f, "a polygon needs at least three distinct vertices"),
+            Error::InvalidAttributeIndex(index) =>
+                write!(f, "invalid attribute index {}", index),
+            Error::UnknownAttribute =>
+                write!(f, "no attribute channel of the given name and type"),
+            Error::BoundaryEdge(index) =>
+                write!(f, "edge {} is a boundary edge", index.offset()),
+            Error::NonTriangularFace(index) =>
+                write!(f, "face {} is not a triangle", index.offset()),
+            Error::DuplicateEdge(a, b) =>
+                write!(f, "vertices {} and {} are already connected", a.offset(), b.offset()),
+            Error::LinkCondition(index) =>
+                write!(f, "collapsing edge {} would make the mesh non-manifold", index.offset()),
+            Error::BridgeEdge(index) =>
+                write!(f, "edge {} has the same face on both sides", index.offset()),
+            Error::IsolatedVertex(index) =>
+                write!(f, "vertex {} is not connected to any edges", index.offset()),
+            Error::VertexNotInFace(vertex, face) =>
+                write!(f, "vertex {} is not part of face {}", vertex.offset(), face.offset()),
+            Error::IndexOverflow =>
+                write!(f, "the index type can't address any more components"),
+        }
+    }
+}
+
+impl<Ix: IndexType> error::Error for Error<Ix> {}
+
+/// Determines how removal methods treat components which still depend
+/// on the component being removed.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RemovalPolicy {
+    /// Refuse to remove the component while anything is connected to it.
+    Refuse,
+    /// Remove every connected component as well.
+    Cascade,
+}
+
+
+/// Determines what happens to the slot of a removed component.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RemovalMode {
+    /// Move the last component of the list into the slot with
+    /// `swap_remove`, keeping the lists packed.
+    SwapRemove,
+    /// Leave a dead component in the slot, so that the indices of all other
+    /// components stay the same until `Mesh::compact` is called.
+    Tombstone,
+}
+
+
 /// Represents the point where two edges meet.
 #[derive(Default, Debug)]
-pub struct Vertex {
+pub struct Vertex<V = (), Ix: IndexType = usize> {
     /// Index of the outgoing edge
-    pub edge_index: EdgeIndex,
-    /// Index of this vertex's attributes
+    pub edge_index: EdgeIndex<Ix>,
+    /// Index of this vertex's attributes, such as its position in
+    /// `Mesh::position_list`
     pub attr_index: VertexAttributeIndex,
+    /// User data carried by this vertex
+    pub data: V,
 }
 
-impl Vertex {
-    pub fn new(edge_index: EdgeIndex) -> Vertex {
+impl<V, Ix: IndexType> Vertex<V, Ix> {
+    pub fn new(edge_index: EdgeIndex<Ix>) -> Vertex<V, Ix>
+        where V: Default
+    {
         Vertex {
-            edge_index: edge_index,
-            attr_index: INVALID_COMPONENT_INDEX
+            edge_index,
+            attr_index: INVALID_COMPONENT_INDEX,
+            data: V::default()
         }
     }
 }
 
-impl Validation for Vertex {
+impl<V, Ix: IndexType> Validation for Vertex<V, Ix> {
     /// A vertex is considered "valid" as long as it as an edge index
     /// other than `INVALID_COMPONENT_INDEX`
     fn is_valid(&self) -> bool {
@@ -81,22 +426,25 @@ impl Validation for Vertex {
 
 /// The principle component in a half-edge mesh.
 #[derive(Default, Debug)]
-pub struct Edge {
+pub struct Edge<E = (), Ix: IndexType = usize> {
     /// The adjacent or 'twin' half-edge
-    pub twin_index: EdgeIndex,
+    pub twin_index: EdgeIndex<Ix>,
     /// The index of the next edge in the loop
-    pub next_index: EdgeIndex,
+    pub next_index: EdgeIndex<Ix>,
     /// The index of the previous edge in the loop
-    pub prev_index: EdgeIndex,
+    pub prev_index: EdgeIndex<Ix>,
 
     /// The index of the face this edge loop defines
-    pub face_index: FaceIndex,
+    pub face_index: FaceIndex<Ix>,
 
     /// The index of the Vertex for this edge.
-    pub vertex_index: VertexIndex,
+    pub vertex_index: VertexIndex<Ix>,
+
+    /// User data carried by this edge
+    pub data: E,
 }
 
-impl Edge {
+impl<E, Ix: IndexType> Edge<E, Ix> {
     /// Returns true when this edge has no twin.
     pub fn is_boundary(&self) -> bool {
         !self.twin_index.is_valid()
@@ -108,7 +456,7 @@ impl Edge {
     }
 }
 
-impl Validation for Edge {
+impl<E, Ix: IndexType> Validation for Edge<E, Ix> {
     /// An edge is generally considered "valid" as long as it has a
     /// vertex and a face index other than `INVALID_COMPONENT_INDEX`,
     /// and "is connected".
@@ -123,20 +471,25 @@ impl Validation for Edge {
 
 /// A face is defined by the looping connectivity of edges.
 #[derive(Default, Debug)]
-pub struct Face {
+pub struct Face<F = (), Ix: IndexType = usize> {
     /// The "root" of an edge loop that defines this face.
-    pub edge_index: EdgeIndex,
+    pub edge_index: EdgeIndex<Ix>,
+    /// User data carried by this face
+    pub data: F,
 }
 
-impl Face {
-    pub fn new(edge_index: EdgeIndex) -> Face {
+impl<F, Ix: IndexType> Face<F, Ix> {
+    pub fn new(edge_index: EdgeIndex<Ix>) -> Face<F, Ix>
+        where F: Default
+    {
         Face {
-            edge_index
+            edge_index,
+            data: F::default()
         }
     }
 }
 
-impl Validation for Face {
+impl<F, Ix: IndexType> Validation for Face<F, Ix> {
     /// A face is considered "valid" as long as it has an edge index
     /// other than `INVALID_COMPONENT_INDEX`
     fn is_valid(&self) -> bool {
@@ -146,29 +499,34 @@ impl Validation for Face {
 
 /// Function set for operations related to the Face struct
 #[derive(Debug)]
-pub struct FaceFn<'mesh> {
-    mesh: &'mesh Mesh,
-    face: &'mesh Face,
-    pub index: FaceIndex
+pub struct FaceFn<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh Mesh<V, E, F, Ix>,
+    face: &'mesh Face<F, Ix>,
+    pub index: FaceIndex<Ix>
 }
 
-impl<'mesh> FaceFn<'mesh> {
+impl<'mesh, V, E, F, Ix: IndexType> FaceFn<'mesh, V, E, F, Ix> {
 
-    pub fn new(index: FaceIndex, mesh: &'mesh Mesh) -> FaceFn {
+    pub fn new(index: FaceIndex<Ix>, mesh: &'mesh Mesh<V, E, F, Ix>) -> FaceFn<'mesh, V, E, F, Ix> {
         FaceFn {
-            mesh: mesh,
+            mesh,
             face: mesh.face(index),
-            index: index
+            index
         }
     }
 
     /// Convert this `FaceFn` to an `EdgeFn`.
-    pub fn edge(self) -> EdgeFn<'mesh> {
+    pub fn edge(self) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn::new(self.face.edge_index, self.mesh)
     }
+
+    /// Returns the user data of this face.
+    pub fn data(&self) -> &'mesh F {
+        &self.face.data
+    }
 }
 
-impl<'mesh> Validation for FaceFn<'mesh> {
+impl<'mesh, V, E, F, Ix: IndexType> Validation for FaceFn<'mesh, V, E, F, Ix> {
     fn is_valid(&self) -> bool {
         self.face.is_valid()
     }
@@ -176,29 +534,83 @@ impl<'mesh> Validation for FaceFn<'mesh> {
 
 /// Function set for operations related to the Vertex struct
 #[derive(Debug)]
-pub struct VertexFn<'mesh> {
-    mesh: &'mesh Mesh,
-    vertex: &'mesh Vertex,
-    pub index: VertexIndex
+pub struct VertexFn<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh Mesh<V, E, F, Ix>,
+    vertex: &'mesh Vertex<V, Ix>,
+    pub index: VertexIndex<Ix>
 }
 
-impl<'mesh> VertexFn<'mesh> {
+impl<'mesh, V, E, F, Ix: IndexType> VertexFn<'mesh, V, E, F, Ix> {
 
-    pub fn new(index: VertexIndex, mesh: &'mesh Mesh) -> VertexFn {
+    pub fn new(index: VertexIndex<Ix>, mesh: &'mesh Mesh<V, E, F, Ix>) -> VertexFn<'mesh, V, E, F, Ix> {
         VertexFn {
-            mesh: mesh,
+            mesh,
             vertex: mesh.vertex(index),
-            index: index
+            index
         }
     }
 
     /// Convert this `VertexFn` to an `EdgeFn`
-    pub fn edge(self) -> EdgeFn<'mesh> {
+    pub fn edge(self) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn::new(self.vertex.edge_index, self.mesh)
     }
+
+    /// Returns the position of this vertex.
+    pub fn position(&self) -> &'mesh Position {
+        self.mesh.position(self.index)
+    }
+
+    /// Returns the user data of this vertex.
+    pub fn data(&self) -> &'mesh V {
+        &self.vertex.data
+    }
+
+    /// Returns an `OutgoingEdges` iterator for the edges starting at this vertex.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Vertex};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// let v3 = mesh.add_vertex(Vertex::default());
+    /// let f1 = mesh.add_triangle(v1, v2, v3);
+    ///
+    /// for eindex in mesh.vertex_fn(v1).outgoing_edges() {
+    ///     assert!(mesh.edge(eindex).vertex_index == v1);
+    /// }
+    /// ```
+    pub fn outgoing_edges(&self) -> OutgoingEdges<'mesh, E, Ix> {
+        OutgoingEdges::new(self.vertex.edge_index, &self.mesh.edge_list)
+    }
+
+    /// Returns an `IncomingEdges` iterator for the edges ending at this vertex.
+    pub fn incoming_edges(&self) -> IncomingEdges<'mesh, E, Ix> {
+        IncomingEdges::new(self.vertex.edge_index, &self.mesh.edge_list)
+    }
+
+    /// Returns an `AdjacentVertices` iterator for the vertices sharing an
+    /// edge with this vertex.
+    pub fn adjacent_vertices(&self) -> AdjacentVertices<'mesh, E, Ix> {
+        AdjacentVertices::new(self.vertex.edge_index, &self.mesh.edge_list)
+    }
+
+    /// Returns an `IncidentFaces` iterator for the faces around this vertex.
+    pub fn incident_faces(&self) -> IncidentFaces<'mesh, E, Ix> {
+        IncidentFaces::new(self.vertex.edge_index, &self.mesh.edge_list)
+    }
+
+    /// Returns true when any edge around this vertex has no twin.
+    pub fn is_boundary(&self) -> bool {
+        let edge_list = &self.mesh.edge_list;
+        self.outgoing_edges().any(|eindex| {
+            let edge = &edge_list[eindex.offset()];
+            edge.is_boundary() || edge_list[edge.prev_index.offset()].is_boundary()
+        })
+    }
 }
 
-impl<'mesh> Validation for VertexFn<'mesh> {
+impl<'mesh, V, E, F, Ix: IndexType> Validation for VertexFn<'mesh, V, E, F, Ix> {
     fn is_valid(&self) -> bool {
         self.vertex.is_valid()
     }
@@ -206,48 +618,53 @@ impl<'mesh> Validation for VertexFn<'mesh> {
 
 /// Function set for operations related to the Edge struct
 #[derive(Debug)]
-pub struct EdgeFn<'mesh> {
-    mesh: &'mesh Mesh,
-    edge: &'mesh Edge,
-    pub index: EdgeIndex
+pub struct EdgeFn<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh Mesh<V, E, F, Ix>,
+    edge: &'mesh Edge<E, Ix>,
+    pub index: EdgeIndex<Ix>
 }
 
-impl<'mesh> EdgeFn<'mesh> {
-    pub fn new(index: EdgeIndex, mesh: &'mesh Mesh) -> EdgeFn {
+impl<'mesh, V, E, F, Ix: IndexType> EdgeFn<'mesh, V, E, F, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, mesh: &'mesh Mesh<V, E, F, Ix>) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn {
-            mesh: mesh,
+            mesh,
             edge: mesh.edge(index),
-            index: index
+            index
         }
     }
 
     /// Convert this `EdgeFn` to an `EdgeFn` of it's next edge
-    pub fn next(self) -> EdgeFn<'mesh> {
+    pub fn next(self) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn::new(self.edge.next_index, self.mesh)
     }
 
     /// Convert this `EdgeFn` to an `EdgeFn` of it's prev edge
-    pub fn prev(self) -> EdgeFn<'mesh> {
+    pub fn prev(self) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn::new(self.edge.prev_index, self.mesh)
     }
 
     /// Convert this `EdgeFn` to an `EdgeFn` of it's twin edge
-    pub fn twin(self) -> EdgeFn<'mesh> {
+    pub fn twin(self) -> EdgeFn<'mesh, V, E, F, Ix> {
         EdgeFn::new(self.edge.twin_index, self.mesh)
     }
 
     /// Convert this `EdgeFn` to an `FaceFn`
-    pub fn face(self) -> FaceFn<'mesh> {
+    pub fn face(self) -> FaceFn<'mesh, V, E, F, Ix> {
         FaceFn::new(self.edge.face_index, self.mesh)
     }
 
     /// Convert this `EdgeFn` to an `VertexFn`
-    pub fn vertex(self) -> VertexFn<'mesh> {
+    pub fn vertex(self) -> VertexFn<'mesh, V, E, F, Ix> {
         VertexFn::new(self.edge.vertex_index, self.mesh)
     }
+
+    /// Returns the user data of this edge.
+    pub fn data(&self) -> &'mesh E {
+        &self.edge.data
+    }
 }
 
-impl<'mesh> Validation for EdgeFn<'mesh> {
+impl<'mesh, V, E, F, Ix: IndexType> Validation for EdgeFn<'mesh, V, E, F, Ix> {
     fn is_valid(&self) -> bool {
         self.edge.is_valid()
     }
@@ -255,25 +672,47 @@ impl<'mesh> Validation for EdgeFn<'mesh> {
 
 /// Implements the fundamental storage operations and represents the principle
 /// grouping of all components.
-pub struct Mesh {
-    pub edge_list: Vec<Edge>,
-    pub vertex_list: Vec<Vertex>,
-    pub face_list: Vec<Face>
+///
+/// Each vertex, edge and face carries a `data` payload of type `V`, `E` and
+/// `F` respectively, none by default. Components created by the mesh itself,
+/// such as the edges of a new polygon or the vertex inserted by
+/// `split_edge`, start out with the default payload.
+///
+/// ```
+/// use hedge::{Mesh, Vertex};
+/// let mut mesh: Mesh<&str, (), u32> = Mesh::default();
+///
+/// let v1 = mesh.add_vertex(Vertex { data: "tip", ..Vertex::default() });
+/// let v2 = mesh.add_vertex(Vertex::default());
+/// let v3 = mesh.add_vertex(Vertex::default());
+/// let f1 = mesh.add_triangle(v1, v2, v3);
+/// mesh.face_mut(f1).unwrap().data = 7;
+///
+/// assert_eq!(*mesh.vertex_fn(v1).data(), "tip");
+/// assert_eq!(*mesh.face_fn(f1).data(), 7);
+/// ```
+pub struct Mesh<V = (), E = (), F = (), Ix: IndexType = usize> {
+    pub edge_list: Vec<Edge<E, Ix>>,
+    pub vertex_list: Vec<Vertex<V, Ix>>,
+    pub face_list: Vec<Face<F, Ix>>,
+    /// Vertex positions, addressed by `Vertex::attr_index`.
+    pub position_list: Vec<Position>,
+    attributes: attributes::AttributeRegistry,
+    removal_mode: RemovalMode,
+    vertex_slots: Slots<Ix>,
+    edge_slots: Slots<Ix>,
+    face_slots: Slots<Ix>
 }
 
-impl fmt::Debug for Mesh {
+impl<V, E, F, Ix: IndexType> fmt::Debug for Mesh<V, E, F, Ix> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Half-Edge Mesh {{ {} vertices, {} edges, {} faces }}",
                self.vertex_list.len(), self.edge_list.len(), self.face_list.len())
     }
 }
 
-impl Mesh {
-    /// Creates a new Mesh with an initial component added to each Vec.
-    ///
-    /// The idea behind having a single invalid component at the front of each
-    /// Vec comes from the blog http://ourmachinery.com/post/defaulting-to-zero/
-    pub fn new() -> Mesh {
+impl<V: Default, E: Default, F: Default, Ix: IndexType> Default for Mesh<V, E, F, Ix> {
+    fn default() -> Mesh<V, E, F, Ix> {
         Mesh {
             edge_list: vec! [
                 Edge::default()
@@ -283,10 +722,33 @@ impl Mesh {
             ],
             face_list: vec! [
                 Face::default()
-            ]
+            ],
+            position_list: vec! [
+                Position::new(0.0, 0.0, 0.0)
+            ],
+            attributes: attributes::AttributeRegistry::default(),
+            removal_mode: RemovalMode::SwapRemove,
+            vertex_slots: Slots::default(),
+            edge_slots: Slots::default(),
+            face_slots: Slots::default()
         }
     }
+}
+
+impl Mesh {
+    /// Creates a new Mesh with an initial component added to each Vec.
+    ///
+    /// The idea behind having a single invalid component at the front of each
+    /// Vec comes from the blog http://ourmachinery.com/post/defaulting-to-zero/
+    ///
+    /// The mesh uses `usize` indices; meshes with another `IndexType` are
+    /// created with `Mesh::default`.
+    pub fn new() -> Mesh {
+        Mesh::default()
+    }
+}
 
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
     /// Mark the two edges as adjacent twins.
     ///
     /// In order for this to be valid each edge should be connected in such a way
@@ -295,7 +757,7 @@ impl Mesh {
     /// So: `A->Next->Vertex == B->Vertex` && `B->Next->Vertex == A->Vertex`
     ///
     /// _In debug builds we assert the provided indices are valid._
-    pub fn set_twin_edges(&mut self, e1: EdgeIndex, e2: EdgeIndex) {
+    pub fn set_twin_edges(&mut self, e1: EdgeIndex<Ix>, e2: EdgeIndex<Ix>) {
         debug_assert!(e1.is_valid());
         debug_assert!(e2.is_valid());
         // TODO: Disabling this for the moment because it would prevent the use
@@ -314,10 +776,39 @@ impl Mesh {
         }
     }
 
+    /// Mark the two edges as adjacent twins, reporting an `Error` instead of
+    /// leaving the mesh in an inconsistent state.
+    ///
+    /// When both edges are already connected to a next edge we also check
+    /// that they run between the same pair of vertices.
+    pub fn try_set_twin_edges(&mut self, e1: EdgeIndex<Ix>, e2: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.check_edge_index(e1)?;
+        self.check_edge_index(e2)?;
+        if e1 == e2 {
+            return Err(Error::MismatchedTwins(e1, e2));
+        }
+        for &(edge, other) in &[(e1, e2), (e2, e1)] {
+            let twin_index = self.edge(edge).twin_index;
+            if twin_index.is_valid() && twin_index != other {
+                return Err(Error::DanglingTwin(edge));
+            }
+        }
+        let (edge1, edge2) = (self.edge(e1), self.edge(e2));
+        if edge1.next_index.is_valid() && edge2.next_index.is_valid() {
+            let matched = edge1.vertex_index == self.edge_fn(e2).next().vertex().index &&
+                edge2.vertex_index == self.edge_fn(e1).next().vertex().index;
+            if !matched {
+                return Err(Error::MismatchedTwins(e1, e2));
+            }
+        }
+        self.set_twin_edges(e1, e2);
+        Ok(())
+    }
+
     /// Connects the two edges as part of an edge loop.
     ///
     /// _In debug builds we assert that neither index is the default index._
-    pub fn connect_edges(&mut self, prev: EdgeIndex, next: EdgeIndex) {
+    pub fn connect_edges(&mut self, prev: EdgeIndex<Ix>, next: EdgeIndex<Ix>) {
         debug_assert!(prev.is_valid());
         debug_assert!(next.is_valid());
         if let Some(ref mut prev_edge) = self.edge_mut(prev) {
@@ -328,16 +819,32 @@ impl Mesh {
         }
     }
 
+    /// Connects the two edges as part of an edge loop, reporting an `Error`
+    /// instead of leaving the mesh in an inconsistent state.
+    ///
+    /// When `prev` has a twin we also check that `next` starts at the vertex
+    /// where `prev` ends.
+    pub fn try_connect_edges(&mut self, prev: EdgeIndex<Ix>, next: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.check_edge_index(prev)?;
+        self.check_edge_index(next)?;
+        if self.edge(prev).twin_index.is_valid() &&
+            self.edge_fn(prev).twin().vertex().index != self.edge(next).vertex_index {
+                return Err(Error::DisjointEdges(prev, next));
+            }
+        self.connect_edges(prev, next);
+        Ok(())
+    }
+
     /// Updates all edges in a loop with the specified face index.
     ///
     /// _In debug builds we assert that each index provided is valid._
-    pub fn assign_face_to_loop(&mut self, face_index: FaceIndex, edge_index: EdgeIndex) {
+    pub fn assign_face_to_loop(&mut self, face_index: FaceIndex<Ix>, edge_index: EdgeIndex<Ix>) {
         debug_assert!(face_index.is_valid());
         debug_assert!(edge_index.is_valid());
         if let Some(ref mut face) = self.face_mut(face_index) {
             face.edge_index = edge_index;
         }
-        let edge_indices: Vec<EdgeIndex> = EdgeLoop::new(edge_index, &self.edge_list).collect();
+        let edge_indices: Vec<EdgeIndex<Ix>> = EdgeLoop::new(edge_index, &self.edge_list).collect();
         for index in edge_indices {
             if let Some(ref mut edge) = self.edge_mut(index) {
                 edge.face_index = face_index;
@@ -345,22 +852,50 @@ impl Mesh {
         }
     }
 
+    /// Updates all edges in a loop with the specified face index, reporting an
+    /// `Error` when the loop is not closed.
+    pub fn try_assign_face_to_loop(&mut self, face_index: FaceIndex<Ix>, edge_index: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.check_face_index(face_index)?;
+        self.check_edge_index(edge_index)?;
+        if !self.loop_is_closed(edge_index) {
+            return Err(Error::OpenEdgeLoop(edge_index));
+        }
+        self.assign_face_to_loop(face_index, edge_index);
+        Ok(())
+    }
+
     /// Create a new edge from the specified vertex.
     ///
     /// _In debug builds we assert that the vertex index is not the default index._
-    pub fn edge_from_vertex(&mut self, vert: VertexIndex) -> EdgeIndex {
+    ///
+    /// Panics when the edge list already holds as many edges as `Ix` can
+    /// address.
+    pub fn edge_from_vertex(&mut self, vert: VertexIndex<Ix>) -> EdgeIndex<Ix>
+        where E: Default
+    {
         debug_assert!(vert.is_valid());
         let result = self.add_edge(Edge {
             twin_index: EdgeIndex::default(),
             next_index: EdgeIndex::default(),
             prev_index: EdgeIndex::default(),
             face_index: FaceIndex::default(),
-            vertex_index: vert
+            vertex_index: vert,
+            data: E::default()
         });
         if let Some(vertex) = self.vertex_mut(vert) {
             vertex.edge_index = result;
         }
-        return result;
+        result
+    }
+
+    /// Create a new edge from the specified vertex, reporting an `Error` when
+    /// the vertex index is invalid.
+    pub fn try_edge_from_vertex(&mut self, vert: VertexIndex<Ix>) -> Result<EdgeIndex<Ix>, Error<Ix>>
+        where E: Default
+    {
+        self.check_vertex_index(vert)?;
+        Self::check_capacity(self.edge_list.len(), 1)?;
+        Ok(self.edge_from_vertex(vert))
     }
 
     /// Create a new edge as a twin of the specified edge
@@ -368,22 +903,49 @@ impl Mesh {
     /// _In debug builds we assert that the twin index is not the default index
     /// and that the twins next index is not the default index (since we need
     /// that edge to find the correct vertex index)._
-    pub fn edge_from_twin(&mut self, twin: EdgeIndex) -> EdgeIndex {
+    ///
+    /// Panics when the edge list already holds as many edges as `Ix` can
+    /// address.
+    pub fn edge_from_twin(&mut self, twin: EdgeIndex<Ix>) -> EdgeIndex<Ix>
+        where E: Default
+    {
         debug_assert!(twin.is_valid());
         debug_assert!(self.edge(twin).next_index.is_valid());
         let vert = self.edge_fn(twin).next().vertex().index;
         let result = self.edge_from_vertex(vert);
         self.set_twin_edges(result, twin);
-        return result;
+        result
+    }
+
+    /// Create a new edge as a twin of the specified edge, reporting an `Error`
+    /// when the twin is not connected or already has a twin of its own.
+    pub fn try_edge_from_twin(&mut self, twin: EdgeIndex<Ix>) -> Result<EdgeIndex<Ix>, Error<Ix>>
+        where E: Default
+    {
+        self.check_edge_index(twin)?;
+        let edge = self.edge(twin);
+        if !edge.next_index.is_valid() {
+            return Err(Error::DisconnectedEdge(twin));
+        }
+        if edge.twin_index.is_valid() {
+            return Err(Error::NonManifoldEdge(twin));
+        }
+        Self::check_capacity(self.edge_list.len(), 1)?;
+        Ok(self.edge_from_twin(twin))
     }
 
     /// Create a new edge connected to the previous edge specified.
     ///
     /// _In debug builds we assert that the indices specified are valid._
-    pub fn extend_edge_loop(&mut self, vert: VertexIndex, prev: EdgeIndex) -> EdgeIndex {
+    ///
+    /// Panics when the edge list already holds as many edges as `Ix` can
+    /// address.
+    pub fn extend_edge_loop(&mut self, vert: VertexIndex<Ix>, prev: EdgeIndex<Ix>) -> EdgeIndex<Ix>
+        where E: Default
+    {
         debug_assert!(vert.is_valid());
         debug_assert!(prev.is_valid());
-        let result = match vert.0 {
+        let result = match vert.offset() {
             INVALID_COMPONENT_INDEX => {
                 debug_assert!(self.edge(prev).twin_index.is_valid());
                 let vert = self.edge_fn(prev).twin().vertex().index;
@@ -392,13 +954,43 @@ impl Mesh {
             _ => self.edge_from_vertex(vert)
         };
         self.connect_edges(prev, result);
-        return result;
+        result
+    }
+
+    /// Create a new edge connected to the previous edge specified, reporting an
+    /// `Error` instead of leaving the mesh in an inconsistent state.
+    ///
+    /// As with `extend_edge_loop` the default vertex index may be passed when
+    /// `prev` has a twin, in which case the vertex of that twin is used.
+    pub fn try_extend_edge_loop(&mut self, vert: VertexIndex<Ix>, prev: EdgeIndex<Ix>) -> Result<EdgeIndex<Ix>, Error<Ix>>
+        where E: Default
+    {
+        self.check_edge_index(prev)?;
+        let prev_edge = self.edge(prev);
+        if prev_edge.next_index.is_valid() {
+            return Err(Error::EdgeAlreadyConnected(prev));
+        }
+        let vert = if vert.is_valid() || !prev_edge.twin_index.is_valid() {
+            vert
+        } else {
+            self.edge_fn(prev).twin().vertex().index
+        };
+        self.check_vertex_index(vert)?;
+        Self::check_capacity(self.edge_list.len(), 1)?;
+        let result = self.edge_from_vertex(vert);
+        self.connect_edges(prev, result);
+        Ok(result)
     }
 
     /// Create a new edge, closing an edge loop, using the `prev` and `next` indices provided.
     ///
     /// _In debug builds we assert that all specified indices are valid._
-    pub fn close_edge_loop(&mut self, vert: VertexIndex, prev: EdgeIndex, next: EdgeIndex) -> EdgeIndex {
+    ///
+    /// Panics when the edge list already holds as many edges as `Ix` can
+    /// address.
+    pub fn close_edge_loop(&mut self, vert: VertexIndex<Ix>, prev: EdgeIndex<Ix>, next: EdgeIndex<Ix>) -> EdgeIndex<Ix>
+        where E: Default
+    {
         debug_assert! {
             vert.is_valid() &&
                 prev.is_valid() &&
@@ -407,145 +999,650 @@ impl Mesh {
         let result = self.edge_from_vertex(vert);
         self.connect_edges(prev, result);
         self.connect_edges(result, next);
-        return result;
+        result
+    }
+
+    /// Create a new edge, closing an edge loop, reporting an `Error` instead of
+    /// leaving the mesh in an inconsistent state.
+    pub fn try_close_edge_loop(&mut self, vert: VertexIndex<Ix>, prev: EdgeIndex<Ix>, next: EdgeIndex<Ix>) -> Result<EdgeIndex<Ix>, Error<Ix>>
+        where E: Default
+    {
+        self.check_vertex_index(vert)?;
+        self.check_edge_index(prev)?;
+        self.check_edge_index(next)?;
+        if self.edge(prev).next_index.is_valid() {
+            return Err(Error::EdgeAlreadyConnected(prev));
+        }
+        if self.edge(next).prev_index.is_valid() {
+            return Err(Error::EdgeAlreadyConnected(next));
+        }
+        Self::check_capacity(self.edge_list.len(), 1)?;
+        Ok(self.close_edge_loop(vert, prev, next))
     }
 
     /// Adds the provided `Edge` to the mesh and returns it's `EdgeIndex`
     ///
     /// _In debug builds we assert that the result is a valid index and
     /// that the edge was added to the list._
-    pub fn add_edge(&mut self, edge: Edge) -> EdgeIndex {
-        let result = EdgeIndex(self.edge_list.len());
+    ///
+    /// Panics when the edge list already holds as many edges as `Ix` can
+    /// address.
+    pub fn add_edge(&mut self, edge: Edge<E, Ix>) -> EdgeIndex<Ix> {
+        let offset = self.edge_list.len();
+        assert!(offset <= <Ix as IndexType>::max().index(), "Mesh::add_edge: edge index overflow");
+        let result = self.edge_index_at(offset);
         self.edge_list.push(edge);
-        return result;
+        self.attributes.push(AttributeDomain::Edge);
+        self.attributes.push(AttributeDomain::UndirectedEdge);
+        result
+    }
+
+    /// Adds the provided `Edge` to the mesh, reporting an `Error` when it
+    /// references components which don't exist or when `Ix` can't address
+    /// another edge.
+    ///
+    /// Default indices are accepted since edges are usually connected after
+    /// they have been added.
+    pub fn try_add_edge(&mut self, edge: Edge<E, Ix>) -> Result<EdgeIndex<Ix>, Error<Ix>> {
+        Self::check_capacity(self.edge_list.len(), 1)?;
+        // The new edge may refer to itself
+        let new_index = self.edge_index_at(self.edge_list.len());
+        for &eindex in &[edge.twin_index, edge.next_index, edge.prev_index] {
+            if eindex != new_index {
+                self.check_optional_edge_index(eindex)?;
+            }
+        }
+        if edge.face_index.is_valid() {
+            self.check_face_index(edge.face_index)?;
+        }
+        if edge.vertex_index.is_valid() {
+            self.check_vertex_index(edge.vertex_index)?;
+        }
+        Ok(self.add_edge(edge))
     }
 
     /// Adds the provided `Vertex` to the mesh and returns it's `VertexIndex`
-    pub fn add_vertex(&mut self, vert: Vertex) -> VertexIndex {
-        let result = VertexIndex(self.vertex_list.len());
+    ///
+    /// A vertex without an attribute index is given a new position at the
+    /// origin. Vertices should not share positions, since removing one of
+    /// them also removes its position.
+    ///
+    /// Panics when the vertex list already holds as many vertices as `Ix`
+    /// can address.
+    pub fn add_vertex(&mut self, mut vert: Vertex<V, Ix>) -> VertexIndex<Ix> {
+        let offset = self.vertex_list.len();
+        assert!(offset <= <Ix as IndexType>::max().index(), "Mesh::add_vertex: vertex index overflow");
+        if vert.attr_index == INVALID_COMPONENT_INDEX {
+            vert.attr_index = self.position_list.len();
+            self.position_list.push(Position::new(0.0, 0.0, 0.0));
+        }
+        let result = self.vertex_index_at(offset);
         self.vertex_list.push(vert);
-        return result;
+        self.attributes.push(AttributeDomain::Vertex);
+        result
+    }
+
+    /// Adds a new unconnected vertex at the given position and returns it's
+    /// `VertexIndex`
+    ///
+    /// Panics when the vertex list already holds as many vertices as `Ix`
+    /// can address.
+    pub fn add_vertex_at(&mut self, position: Position) -> VertexIndex<Ix>
+        where V: Default
+    {
+        let result = self.add_vertex(Vertex::default());
+        self.set_position(result, position);
+        result
+    }
+
+    /// Adds a new unconnected vertex at the given position, reporting an
+    /// `Error` when `Ix` can't address another vertex.
+    pub fn try_add_vertex_at(&mut self, position: Position) -> Result<VertexIndex<Ix>, Error<Ix>>
+        where V: Default
+    {
+        Self::check_capacity(self.vertex_list.len(), 1)?;
+        Ok(self.add_vertex_at(position))
+    }
+
+    /// Adds the provided `Vertex` to the mesh, reporting an `Error` when it
+    /// references an edge which doesn't exist or when `Ix` can't address
+    /// another vertex.
+    pub fn try_add_vertex(&mut self, vert: Vertex<V, Ix>) -> Result<VertexIndex<Ix>, Error<Ix>> {
+        Self::check_capacity(self.vertex_list.len(), 1)?;
+        self.check_optional_edge_index(vert.edge_index)?;
+        if vert.attr_index >= self.position_list.len() {
+            return Err(Error::InvalidAttributeIndex(vert.attr_index));
+        }
+        Ok(self.add_vertex(vert))
+    }
+
+    /// Sets the position of the specified vertex, giving it a new position
+    /// when it doesn't have one yet.
+    ///
+    /// _In debug builds we assert that the vertex index is valid._
+    pub fn set_position(&mut self, index: VertexIndex<Ix>, position: Position) {
+        debug_assert!(index.is_valid());
+        let attr_index = self.vertex(index).attr_index;
+        if let Some(target) = self.position_list.get_mut(attr_index) {
+            if attr_index != INVALID_COMPONENT_INDEX {
+                *target = position;
+                return;
+            }
+        }
+        let attr_index = self.position_list.len();
+        if let Some(vertex) = self.vertex_mut(index) {
+            vertex.attr_index = attr_index;
+            self.position_list.push(position);
+        }
+    }
+
+    /// Sets the position of the specified vertex, reporting an `Error` when
+    /// the vertex index is invalid.
+    pub fn try_set_position(&mut self, index: VertexIndex<Ix>, position: Position) -> Result<(), Error<Ix>> {
+        self.check_vertex_index(index)?;
+        self.set_position(index, position);
+        Ok(())
     }
 
     /// Adds the provided `Face` to the mesh and returns it's `FaceIndex`
     ///
     /// _In debug builds we assert that the result is a valid index and
     /// that the face was added to the list._
-    pub fn add_face(&mut self, face: Face) -> FaceIndex {
-        let result = FaceIndex(self.face_list.len());
+    ///
+    /// Panics when the face list already holds as many faces as `Ix` can
+    /// address.
+    pub fn add_face(&mut self, face: Face<F, Ix>) -> FaceIndex<Ix> {
+        let offset = self.face_list.len();
+        assert!(offset <= <Ix as IndexType>::max().index(), "Mesh::add_face: face index overflow");
+        let result = self.face_index_at(offset);
         self.face_list.push(face);
-        return result;
+        self.attributes.push(AttributeDomain::Face);
+        result
+    }
+
+    /// Adds the provided `Face` to the mesh, reporting an `Error` when it
+    /// references an edge which doesn't exist or when `Ix` can't address
+    /// another face.
+    pub fn try_add_face(&mut self, face: Face<F, Ix>) -> Result<FaceIndex<Ix>, Error<Ix>> {
+        Self::check_capacity(self.face_list.len(), 1)?;
+        self.check_optional_edge_index(face.edge_index)?;
+        Ok(self.add_face(face))
+    }
+
+    /// Returns how the removal methods and the operators built on them
+    /// treat the slots of removed components.
+    pub fn removal_mode(&self) -> RemovalMode {
+        self.removal_mode
+    }
+
+    /// Sets how the slots of removed components are treated from now on.
+    ///
+    /// ```
+    /// use hedge::{Mesh, RemovalMode, RemovalPolicy, Vertex};
+    /// let mut mesh = Mesh::new();
+    /// mesh.set_removal_mode(RemovalMode::Tombstone);
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// mesh.remove_vertex(v1, RemovalPolicy::Refuse).unwrap();
+    ///
+    /// // v2 keeps its index until the mesh is compacted
+    /// assert!(mesh.is_current_vertex(v2));
+    /// assert_eq!(mesh.vertex_count(), 1);
+    /// ```
+    pub fn set_removal_mode(&mut self, mode: RemovalMode) {
+        self.removal_mode = mode;
+    }
+
+    /// Removes the vertex at the specified index.
+    ///
+    /// When the vertex still has edges connected to it the `policy` decides
+    /// whether we refuse to remove it, returning `Error::VertexInUse`, or
+    /// remove every face and edge incident to it as well.
+    ///
+    /// In the default `RemovalMode::SwapRemove` the last vertex in the list
+    /// moves into the slot of the removed vertex. Indices to either of them
+    /// become stale; `Mesh::vertex_index_at` returns the new index of the
+    /// moved vertex. With `RemovalMode::Tombstone` only indices to the
+    /// removed vertex become stale.
+    ///
+    /// Unlike the other removal methods an invalid index is reported as an
+    /// `Error` in every build.
+    pub fn remove_vertex(&mut self, index: VertexIndex<Ix>, policy: RemovalPolicy) -> Result<(), Error<Ix>>
+        where V: Default, E: Default, F: Default
+    {
+        self.check_vertex_index(index)?;
+        let outgoing: Vec<EdgeIndex<Ix>> = self.vertex_fn(index).outgoing_edges().collect();
+        if !outgoing.is_empty() && policy == RemovalPolicy::Refuse {
+            return Err(Error::VertexInUse(index));
+        }
+
+        // Trade places with the last vertex up front, so that the removals
+        // below never come across a vertex index which has since moved.
+        // We don't circulate the vertices here since earlier removals may
+        // have left their edges disconnected from one another.
+        if self.removal_mode == RemovalMode::SwapRemove {
+            let last_index = self.vertex_index_at(self.vertex_list.len() - 1);
+            self.vertex_slots.swap_remove(index.offset(), last_index.offset());
+            let (moved_index, removed_index) = (self.vertex_index_at(index.offset()), self.vertex_index_at(last_index.offset()));
+            for edge in &mut self.edge_list {
+                if edge.vertex_index == last_index {
+                    edge.vertex_index = moved_index;
+                } else if edge.vertex_index == index {
+                    edge.vertex_index = removed_index;
+                }
+            }
+            self.vertex_list.swap(index.offset(), last_index.offset());
+        }
+
+        if !outgoing.is_empty() {
+            let mut faces = Vec::new();
+            let mut edges = Vec::new();
+            for &eindex in &outgoing {
+                let edge = self.edge(eindex);
+                faces.push(edge.face_index);
+                edges.push(eindex);
+                edges.push(edge.twin_index);
+                edges.push(edge.prev_index);
+            }
+            faces.retain(|f| f.is_valid());
+            edges.retain(|e| e.is_valid());
+            self.detach_one_ring(&faces, &edges);
+            self.remove_detached(faces, edges);
+        }
+
+        let removed_vertex = match self.removal_mode {
+            RemovalMode::SwapRemove => {
+                self.attributes.swap_remove(AttributeDomain::Vertex, index.offset());
+                self.vertex_list.pop().unwrap()
+            },
+            RemovalMode::Tombstone => {
+                self.vertex_slots.bury(index.offset());
+                mem::take(&mut self.vertex_list[index.offset()])
+            },
+        };
+        self.remove_position(removed_vertex.attr_index);
+        Ok(())
+    }
+
+    /// Disconnects the faces and edges around a vertex from the edges which
+    /// remain once they are removed.
+    ///
+    /// The remaining edges of the faces become boundary edges, and vertices
+    /// whose edge is about to be removed are given one of them instead.
+    fn detach_one_ring(&mut self, faces: &[FaceIndex<Ix>], edges: &[EdgeIndex<Ix>]) {
+        let mut remaining = Vec::new();
+        for &findex in faces {
+            let root = self.face(findex).edge_index;
+            remaining.extend(EdgeLoop::new(root, &self.edge_list).filter(|e| !edges.contains(e)));
+        }
+
+        for &eindex in &remaining {
+            let twin_index = self.edge(eindex).twin_index;
+            for &candidate in &[eindex, twin_index] {
+                if !candidate.is_valid() || edges.contains(&candidate) {
+                    continue;
+                }
+                let vindex = self.edge(candidate).vertex_index;
+                if let Some(vertex) = self.vertex_mut(vindex) {
+                    if edges.contains(&vertex.edge_index) {
+                        vertex.edge_index = candidate;
+                    }
+                }
+            }
+
+            let edge = &mut self.edge_list[eindex.offset()];
+            edge.face_index = FaceIndex::default();
+            if edges.contains(&edge.next_index) {
+                edge.next_index = EdgeIndex::default();
+            }
+            if edges.contains(&edge.prev_index) {
+                edge.prev_index = EdgeIndex::default();
+            }
+        }
+
+        // Earlier removals may have left edges of other faces linked to the
+        // removed ones, which the loops above never reach.
+        for &eindex in edges {
+            let edge = self.edge(eindex);
+            let (twin_index, next_index, prev_index) = (edge.twin_index, edge.next_index, edge.prev_index);
+            let findex = edge.face_index;
+            if let Some(twin) = self.edge_mut(twin_index) {
+                if twin.twin_index == eindex {
+                    twin.twin_index = EdgeIndex::default();
+                }
+            }
+            if let Some(next) = self.edge_mut(next_index) {
+                if next.prev_index == eindex {
+                    next.prev_index = EdgeIndex::default();
+                }
+            }
+            if let Some(prev) = self.edge_mut(prev_index) {
+                if prev.next_index == eindex {
+                    prev.next_index = EdgeIndex::default();
+                }
+            }
+            if faces.contains(&findex) {
+                continue;
+            }
+            let root = [next_index, prev_index].iter().cloned()
+                .find(|e| e.is_valid() && !edges.contains(e))
+                .unwrap_or_default();
+            if let Some(face) = self.face_mut(findex) {
+                if face.edge_index == eindex {
+                    face.edge_index = root;
+                }
+            }
+        }
+    }
+
+    /// Removes faces and edges which have already been detached from the
+    /// rest of the mesh.
+    ///
+    /// No remaining component may refer to the removed ones, except that
+    /// vertices left referring to a removed edge lose their edge.
+    fn remove_detached(&mut self, mut faces: Vec<FaceIndex<Ix>>, mut edges: Vec<EdgeIndex<Ix>>)
+        where E: Default, F: Default
+    {
+        // The root edge of a removed face may since have been given to
+        // another face, which must keep its edges.
+        for &findex in &faces {
+            self.face_list[findex.offset()].edge_index = EdgeIndex::default();
+        }
+        for &eindex in &edges {
+            let vindex = self.edge(eindex).vertex_index;
+            if let Some(vertex) = self.vertex_mut(vindex) {
+                if vertex.edge_index == eindex {
+                    vertex.edge_index = EdgeIndex::default();
+                }
+            }
+            let edge = &mut self.edge_list[eindex.offset()];
+            edge.twin_index = EdgeIndex::default();
+            edge.next_index = EdgeIndex::default();
+            edge.prev_index = EdgeIndex::default();
+            edge.face_index = FaceIndex::default();
+        }
+
+        // Removing in descending order guarantees that `swap_remove` never
+        // moves a component we still intend to remove.
+        faces.sort_by_key(|f| cmp::Reverse(f.offset()));
+        faces.dedup();
+        for findex in faces {
+            self.remove_face(findex);
+        }
+        edges.sort_by_key(|e| cmp::Reverse(e.offset()));
+        edges.dedup();
+        for eindex in edges {
+            self.remove_edge(eindex);
+        }
+    }
+
+    /// Removes a position from `position_list`, updating the vertex which
+    /// refers to the position moved into its place.
+    fn remove_position(&mut self, attr_index: VertexAttributeIndex) {
+        if attr_index == INVALID_COMPONENT_INDEX || attr_index >= self.position_list.len() {
+            return;
+        }
+        let swapped_index = self.position_list.len() - 1;
+        self.position_list.swap_remove(attr_index);
+        if attr_index != swapped_index {
+            for vertex in &mut self.vertex_list {
+                if vertex.attr_index == swapped_index {
+                    vertex.attr_index = attr_index;
+                }
+            }
+        }
+    }
+
+    fn check_vertex_index(&self, index: VertexIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.get_vertex(index).map(|_| ()).ok_or(Error::InvalidVertexIndex(index))
+    }
+
+    fn check_edge_index(&self, index: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.get_edge(index).map(|_| ()).ok_or(Error::InvalidEdgeIndex(index))
+    }
+
+    /// Reports `Error::IndexOverflow` unless `Ix` can address `count` more
+    /// components after the `len` components of a list.
+    fn check_capacity(len: usize, count: usize) -> Result<(), Error<Ix>> {
+        if len + count - 1 <= <Ix as IndexType>::max().index() {
+            Ok(())
+        } else {
+            Err(Error::IndexOverflow)
+        }
+    }
+
+    /// Like `check_edge_index` but also accepts the default index.
+    fn check_optional_edge_index(&self, index: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        if index.is_valid() {
+            self.check_edge_index(index)
+        } else {
+            Ok(())
+        }
     }
 
-    pub fn remove_vertex(&mut self, index: VertexIndex) {
-        // TODO: In order to remove a vertex you need to circulate over
-        //       all connected edges and either remove them, or refuse
-        //       remove this vertex until those edges are removed first.
-        unimplemented!()
+    fn check_face_index(&self, index: FaceIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.get_face(index).map(|_| ()).ok_or(Error::InvalidFaceIndex(index))
     }
 
-    // TODO: dissolve_vertex
+    /// Follows next edges from `root`, giving up once every edge in the mesh
+    /// has been visited.
+    fn loop_is_closed(&self, root: EdgeIndex<Ix>) -> bool {
+        let mut current = root;
+        for _ in 0 .. self.edge_list.len() {
+            current = self.edge(current).next_index;
+            if current == root {
+                return true;
+            }
+            if !current.is_valid() {
+                break;
+            }
+        }
+        false
+    }
 
-    // TODO: Looking over this I am definitely missing a bunch of edge cases if
-    //       I don't ensure that the related components are valid.
-    pub fn remove_edge(&mut self, index: EdgeIndex) {
+    /// Removes the edge at the specified index.
+    ///
+    /// Neighbouring edges have their references to the removed edge cleared,
+    /// and the face and vertex of the removed edge are updated to use another
+    /// edge when one is available.
+    ///
+    /// The last edge in the list is moved into the vacated slot (`swap_remove`)
+    /// and all references to it are updated accordingly. Indices held
+    /// elsewhere to either edge become stale. With `RemovalMode::Tombstone`
+    /// the slot is left dead instead.
+    ///
+    /// Stale indices, including those of edges which were already removed,
+    /// are ignored.
+    ///
+    /// _In debug builds we assert that the provided index is valid._
+    pub fn remove_edge(&mut self, index: EdgeIndex<Ix>)
+        where E: Default
+    {
         debug_assert!(index.is_valid());
-        let removed_edge = self.edge_list.swap_remove(index.0);
+        if !self.is_current_edge(index) {
+            return;
+        }
+        let (removed_edge, swapped_index) = match self.removal_mode {
+            RemovalMode::SwapRemove => {
+                let swapped_index = self.edge_index_at(self.edge_list.len() - 1);
+                self.edge_slots.swap_remove(index.offset(), swapped_index.offset());
+                self.attributes.swap_remove(AttributeDomain::Edge, index.offset());
+                self.attributes.swap_remove(AttributeDomain::UndirectedEdge, index.offset());
+                (self.edge_list.swap_remove(index.offset()), swapped_index)
+            },
+            RemovalMode::Tombstone => {
+                self.edge_slots.bury(index.offset());
+                (mem::take(&mut self.edge_list[index.offset()]), index)
+            },
+        };
+        let moved_index = self.edge_index_at(index.offset());
+
+        // Indices held by the removed edge may refer to the edge that was
+        // just moved into its place.
+        let moved = |eindex: EdgeIndex<Ix>| {
+            if eindex == swapped_index { moved_index } else { eindex }
+        };
 
         // Update components affected by removal
-        if let Some(ref mut next) = self.edge_mut(removed_edge.next_index) {
+        if let Some(next) = self.edge_mut(moved(removed_edge.next_index)) {
             next.prev_index = EdgeIndex::default();
         }
-        if let Some(ref mut prev) = self.edge_mut(removed_edge.prev_index) {
+        if let Some(prev) = self.edge_mut(moved(removed_edge.prev_index)) {
             prev.next_index = EdgeIndex::default();
         }
-        if let Some(ref mut twin) = self.edge_mut(removed_edge.twin_index) {
+        if let Some(twin) = self.edge_mut(moved(removed_edge.twin_index)) {
             twin.twin_index = EdgeIndex::default();
         }
-        if let Some(ref mut face) = self.face_mut(removed_edge.face_index) {
+        if let Some(face) = self.face_mut(removed_edge.face_index) {
             if face.edge_index == index {
-                face.edge_index = removed_edge.next_index;
+                face.edge_index = moved(removed_edge.next_index);
             }
         }
         // updating the vertex can be a little tricky
         let vertex_edge_index = self.vertex(removed_edge.vertex_index).edge_index;
         if vertex_edge_index == index {
-            let eindex = if removed_edge.is_boundary() {
+            let candidate = if removed_edge.is_boundary() {
                 // when this is a boundary edge, then we can check if our previous
-                // edge has a twin. When that's the case, the vertex of the twin
-                // of the previous edge should be this same vertex, so we can
-                // update the vertex with the index of that edge.
-                let vindex = self.edge_fn(removed_edge.prev_index).twin().vertex().index;
-                debug_assert!(removed_edge.vertex_index == vindex);
-                self.edge(removed_edge.prev_index).twin_index
+                // edge has a twin. When that's the case, the twin of the previous
+                // edge should start at this same vertex.
+                self.edge(moved(removed_edge.prev_index)).twin_index
             } else {
-                // when this is not a boundary edge then the vertex of the twins
-                // next edge should be this same vertex.
-                let vindex = self.edge_fn(removed_edge.twin_index).next().vertex().index;
-                debug_assert!(removed_edge.vertex_index == vindex);
-                self.edge(removed_edge.twin_index).next_index
+                // when this is not a boundary edge then the twins next edge
+                // should start at this same vertex.
+                self.edge(moved(removed_edge.twin_index)).next_index
             };
-            if let Some(ref mut vertex) = self.vertex_mut(removed_edge.vertex_index) {
+            // If neither is the case then the vertex is left without an edge.
+            let eindex = if candidate != index &&
+                self.edge(moved(candidate)).vertex_index == removed_edge.vertex_index {
+                    moved(candidate)
+                } else {
+                    EdgeIndex::default()
+                };
+            if let Some(vertex) = self.vertex_mut(removed_edge.vertex_index) {
                 vertex.edge_index = eindex;
             }
         }
 
+        if index == swapped_index {
+            return;
+        }
+
         // Update components affected by the swap
-        let next_index = self.edge(index).next_index;
-        if let Some(ref mut next) = self.edge_mut(next_index) {
-            next.prev_index = index;
+        let next_index = self.edge(moved_index).next_index;
+        if let Some(next) = self.edge_mut(next_index) {
+            next.prev_index = moved_index;
         }
-        let prev_index = self.edge(index).prev_index;
-        if let Some(ref mut prev) = self.edge_mut(prev_index) {
-            prev.next_index = index;
+        let prev_index = self.edge(moved_index).prev_index;
+        if let Some(prev) = self.edge_mut(prev_index) {
+            prev.next_index = moved_index;
         }
-        let twin_index = self.edge(index).twin_index;
-        if let Some(ref mut twin) = self.edge_mut(twin_index) {
-            twin.twin_index = index;
+        let twin_index = self.edge(moved_index).twin_index;
+        if let Some(twin) = self.edge_mut(twin_index) {
+            twin.twin_index = moved_index;
         }
-        let swapped_index = EdgeIndex(self.edge_list.len());
-        let face_index = self.edge(index).face_index;
-        if let Some(ref mut face) = self.face_mut(face_index) {
+        let face_index = self.edge(moved_index).face_index;
+        if let Some(face) = self.face_mut(face_index) {
             if face.edge_index == swapped_index {
-                face.edge_index = index;
+                face.edge_index = moved_index;
             }
         }
-        let swapped_vertex_index = self.edge(index).vertex_index;
-        if let Some(ref mut vertex) = self.vertex_mut(swapped_vertex_index) {
+        let swapped_vertex_index = self.edge(moved_index).vertex_index;
+        if let Some(vertex) = self.vertex_mut(swapped_vertex_index) {
             if vertex.edge_index == swapped_index {
-                vertex.edge_index = index;
+                vertex.edge_index = moved_index;
             }
         }
     }
 
-    // TODO: dissolve_edge, collapse_edge
+    /// Removes the edge at the specified index, reporting an `Error` when the
+    /// index is invalid.
+    pub fn try_remove_edge(&mut self, index: EdgeIndex<Ix>) -> Result<(), Error<Ix>>
+        where E: Default
+    {
+        self.check_edge_index(index)?;
+        self.remove_edge(index);
+        Ok(())
+    }
 
-    pub fn remove_face(&mut self, index: FaceIndex) {
+    /// Removes the face at the specified index.
+    ///
+    /// The edges of the removed face are kept but no longer reference a face.
+    /// The last face in the list is moved into the vacated slot (`swap_remove`)
+    /// and the edges of its loop are updated accordingly. Indices held
+    /// elsewhere to either face become stale. With `RemovalMode::Tombstone`
+    /// the slot is left dead instead.
+    ///
+    /// Stale indices, including those of faces which were already removed,
+    /// are ignored.
+    ///
+    /// _In debug builds we assert that the provided index is valid._
+    pub fn remove_face(&mut self, index: FaceIndex<Ix>)
+        where F: Default
+    {
         debug_assert!(index.is_valid());
-        let removed_face = self.face_list.swap_remove(index.0);
+        if !self.is_current_face(index) {
+            return;
+        }
+        let (removed_face, swapped_index) = match self.removal_mode {
+            RemovalMode::SwapRemove => {
+                let swapped_index = self.face_index_at(self.face_list.len() - 1);
+                self.face_slots.swap_remove(index.offset(), swapped_index.offset());
+                self.attributes.swap_remove(AttributeDomain::Face, index.offset());
+                (self.face_list.swap_remove(index.offset()), swapped_index)
+            },
+            RemovalMode::Tombstone => {
+                self.face_slots.bury(index.offset());
+                (mem::take(&mut self.face_list[index.offset()]), index)
+            },
+        };
 
-        let edges_of_removed: Vec<EdgeIndex> =
+        let edges_of_removed: Vec<EdgeIndex<Ix>> =
             EdgeLoop::new(removed_face.edge_index, &self.edge_list).collect();
         for eindex in edges_of_removed {
-            self.edge_mut(eindex).map(|e| e.face_index = FaceIndex::default());
+            if let Some(e) = self.edge_mut(eindex) {
+                e.face_index = FaceIndex::default();
+            }
         }
 
-        let edges_of_swapped: Vec<EdgeIndex> = {
-            let swapped_face = self.face(index);
+        if index == swapped_index {
+            return;
+        }
+
+        let moved_index = self.face_index_at(index.offset());
+        let edges_of_swapped: Vec<EdgeIndex<Ix>> = {
+            let swapped_face = self.face(moved_index);
             self.edges(swapped_face).collect()
         };
         for eindex in edges_of_swapped {
-            self.edge_mut(eindex).map(|e| e.face_index = index);
+            if let Some(e) = self.edge_mut(eindex) {
+                e.face_index = moved_index;
+            }
         }
     }
 
-    // TODO: dissolve_face, collapse_face
+    /// Removes the face at the specified index, reporting an `Error` when the
+    /// index is invalid.
+    pub fn try_remove_face(&mut self, index: FaceIndex<Ix>) -> Result<(), Error<Ix>>
+        where F: Default
+    {
+        self.check_face_index(index)?;
+        self.remove_face(index);
+        Ok(())
+    }
+
+    // TODO: collapse_face
 
     /// Creates a new face and associated edges with the given vertex indices.
     /// Returns the index of the newly added face.
     ///
     /// _In debug builds we assert that all provided indices are valid._
-    pub fn add_triangle(&mut self, a: VertexIndex, b: VertexIndex, c: VertexIndex) -> FaceIndex {
+    ///
+    /// Panics when `Ix` can't address the new edges and face; use
+    /// `try_add_polygon` to have this reported as `Error::IndexOverflow`.
+    pub fn add_triangle(&mut self, a: VertexIndex<Ix>, b: VertexIndex<Ix>, c: VertexIndex<Ix>) -> FaceIndex<Ix>
+        where E: Default, F: Default
+    {
         debug_assert!(a.is_valid());
         debug_assert!(b.is_valid());
         debug_assert!(c.is_valid());
@@ -556,18 +1653,37 @@ impl Mesh {
 
         let result = self.add_face(Face::new(e1));
 
-        self.edge_mut(e1).map(|e| e.face_index = result);
-        self.edge_mut(e2).map(|e| e.face_index = result);
-        self.edge_mut(e3).map(|e| e.face_index = result);
+        if let Some(e) = self.edge_mut(e1) {
+            e.face_index = result;
+        }
+        if let Some(e) = self.edge_mut(e2) {
+            e.face_index = result;
+        }
+        if let Some(e) = self.edge_mut(e3) {
+            e.face_index = result;
+        }
+
+        result
+    }
 
-        return result;
+    /// Creates a new triangle, reporting an `Error` when any vertex index is
+    /// invalid or the triangle would be degenerate.
+    pub fn try_add_triangle(&mut self, a: VertexIndex<Ix>, b: VertexIndex<Ix>, c: VertexIndex<Ix>) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.try_add_polygon(&[a, b, c])
     }
 
     /// Creates a new face and associated edges with the given a vertex index and a twin edge index.
     /// Returns the index of the newly added face.
     ///
     /// _In debug builds we assert that the all provided indices are valid._
-    pub fn add_adjacent_triangle(&mut self, c: VertexIndex, twin_edge: EdgeIndex) -> FaceIndex {
+    ///
+    /// Panics when `Ix` can't address the new edges and face; use
+    /// `try_add_adjacent_triangle` to have this reported as `Error::IndexOverflow`.
+    pub fn add_adjacent_triangle(&mut self, c: VertexIndex<Ix>, twin_edge: EdgeIndex<Ix>) -> FaceIndex<Ix>
+        where E: Default, F: Default
+    {
         debug_assert!(c.is_valid());
         debug_assert!(twin_edge.is_valid());
 
@@ -578,11 +1694,42 @@ impl Mesh {
 
         let result = self.add_face(Face::new(e1));
 
-        self.edge_mut(e1).map(|e| e.face_index = result);
-        self.edge_mut(e2).map(|e| e.face_index = result);
-        self.edge_mut(e3).map(|e| e.face_index = result);
+        if let Some(e) = self.edge_mut(e1) {
+            e.face_index = result;
+        }
+        if let Some(e) = self.edge_mut(e2) {
+            e.face_index = result;
+        }
+        if let Some(e) = self.edge_mut(e3) {
+            e.face_index = result;
+        }
 
-        return result;
+        result
+    }
+
+    /// Creates a new triangle adjacent to `twin_edge`, reporting an `Error`
+    /// instead of leaving the mesh in an inconsistent state.
+    ///
+    /// The twin edge must be part of an edge loop and may not already have a
+    /// twin, since a third face on a single edge is non-manifold.
+    pub fn try_add_adjacent_triangle(&mut self, c: VertexIndex<Ix>, twin_edge: EdgeIndex<Ix>) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.check_vertex_index(c)?;
+        self.check_edge_index(twin_edge)?;
+        let edge = self.edge(twin_edge);
+        if !edge.next_index.is_valid() {
+            return Err(Error::DisconnectedEdge(twin_edge));
+        }
+        if edge.twin_index.is_valid() {
+            return Err(Error::NonManifoldEdge(twin_edge));
+        }
+        if c == edge.vertex_index || c == self.edge_fn(twin_edge).next().vertex().index {
+            return Err(Error::DegeneratePolygon);
+        }
+        Self::check_capacity(self.edge_list.len(), 3)?;
+        Self::check_capacity(self.face_list.len(), 1)?;
+        Ok(self.add_adjacent_triangle(c, twin_edge))
     }
 
     /// Create a new face given a slice of vertex indices.
@@ -592,7 +1739,12 @@ impl Mesh {
     /// method instead.
     ///
     /// _In debug builds we assert that all vertex indices are valid._
-    pub fn add_polygon(&mut self, verts: &[VertexIndex]) -> FaceIndex {
+    ///
+    /// Panics when `Ix` can't address the new edges and face; use
+    /// `try_add_polygon` to have this reported as `Error::IndexOverflow`.
+    pub fn add_polygon(&mut self, verts: &[VertexIndex<Ix>]) -> FaceIndex<Ix>
+        where E: Default, F: Default
+    {
         debug_assert! {
             verts.iter().all(|v| v.is_valid())
         };
@@ -604,18 +1756,55 @@ impl Mesh {
 
                 let root_edge_index = self.edge_from_vertex(verts[0]);
                 let mut last_edge_index = root_edge_index;
-                for i in 1 .. vert_count - 2 {
-                    last_edge_index = self.extend_edge_loop(verts[i], last_edge_index);
+                for &vert in &verts[1 .. vert_count - 1] {
+                    last_edge_index = self.extend_edge_loop(vert, last_edge_index);
                 }
                 self.close_edge_loop(verts[vert_count-1], last_edge_index, root_edge_index);
 
                 self.assign_face_to_loop(face_index, root_edge_index);
 
-                return face_index;
+                face_index
             }
         }
     }
 
+    /// Create a new face given a slice of vertex indices, reporting an `Error`
+    /// when any index is invalid or the polygon would be degenerate.
+    ///
+    /// A polygon needs at least three vertices, none of which are repeated,
+    /// and `Ix` has to be able to address its edges and face.
+    pub fn try_add_polygon(&mut self, verts: &[VertexIndex<Ix>]) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        if verts.len() < 3 {
+            return Err(Error::DegeneratePolygon);
+        }
+        for (i, &vert) in verts.iter().enumerate() {
+            self.check_vertex_index(vert)?;
+            if verts[.. i].contains(&vert) {
+                return Err(Error::DegeneratePolygon);
+            }
+        }
+        Self::check_capacity(self.edge_list.len(), verts.len())?;
+        Self::check_capacity(self.face_list.len(), 1)?;
+        Ok(self.add_polygon(verts))
+    }
+
+    /// The number of vertices, leaving out the default vertex and tombstones.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_list.len() - 1 - self.vertex_slots.dead_count
+    }
+
+    /// The number of edges, leaving out the default edge and tombstones.
+    pub fn edge_count(&self) -> usize {
+        self.edge_list.len() - 1 - self.edge_slots.dead_count
+    }
+
+    /// The number of faces, leaving out the default face and tombstones.
+    pub fn face_count(&self) -> usize {
+        self.face_list.len() - 1 - self.face_slots.dead_count
+    }
+
     /// Returns a `Faces` iterator for this mesh.
     ///
     /// ```
@@ -624,8 +1813,8 @@ impl Mesh {
     ///    let face = mesh.face(index);
     /// }
     /// ```
-    pub fn faces(&self) -> Faces {
-        Faces::new(self.face_list.len())
+    pub fn faces(&self) -> Faces<'_, V, E, F, Ix> {
+        Faces::new(self)
     }
 
     /// Returns an `EdgeLoop` iterator for the edges around the specified face.
@@ -639,7 +1828,7 @@ impl Mesh {
     ///    }
     /// }
     /// ```
-    pub fn edges(&self, face: &Face) -> EdgeLoop {
+    pub fn edges(&self, face: &Face<F, Ix>) -> EdgeLoop<'_, E, Ix> {
         EdgeLoop::new(face.edge_index, &self.edge_list)
     }
 
@@ -654,18 +1843,33 @@ impl Mesh {
     ///    }
     /// }
     /// ```
-    pub fn vertices(&self, face: &Face) -> EdgeLoopVertices {
+    pub fn vertices(&self, face: &Face<F, Ix>) -> EdgeLoopVertices<'_, E, Ix> {
         EdgeLoopVertices::new(face.edge_index, &self.edge_list)
     }
 
-    pub fn face(&self, index: FaceIndex) -> &Face {
-        if let Some(result) = self.face_list.get(index.0) {
-            result
+    /// Returns the `Face` for the provided index.
+    ///
+    /// Indices which are out of bounds or stale, referring to a face
+    /// which has since been removed or moved, yield the default face;
+    /// `Mesh::get_face` tells them apart from current indices.
+    pub fn face(&self, index: FaceIndex<Ix>) -> &Face<F, Ix> {
+        if self.is_current_face(index) {
+            &self.face_list[index.offset()]
         } else {
             &self.face_list[0]
         }
     }
 
+    /// Returns the `Face` for the provided index, or `None` for the default
+    /// index as well as for indices which are out of bounds or stale.
+    pub fn get_face(&self, index: FaceIndex<Ix>) -> Option<&Face<F, Ix>> {
+        if index.is_valid() && self.is_current_face(index) {
+            self.face_list.get(index.offset())
+        } else {
+            None
+        }
+    }
+
     /// Returns a `FaceFn` for the given index.
     ///
     /// ```
@@ -680,169 +1884,473 @@ impl Mesh {
     ///
     /// assert!(mesh.face_fn(f1).edge().next().vertex().index == v2);
     /// ```
-    pub fn face_fn(&self, index: FaceIndex) -> FaceFn {
-        FaceFn::new(index, &self)
+    ///
+    /// Like `Mesh::face`, a stale index yields a function set for the
+    /// default face, which is not valid.
+    pub fn face_fn(&self, index: FaceIndex<Ix>) -> FaceFn<'_, V, E, F, Ix> {
+        FaceFn::new(index, self)
+    }
+
+    /// Returns a `FaceFn` for the given index, or `None` when the index
+    /// isn't current.
+    pub fn get_face_fn(&self, index: FaceIndex<Ix>) -> Option<FaceFn<'_, V, E, F, Ix>> {
+        self.get_face(index).map(|_| FaceFn::new(index, self))
     }
 
     /// Obtains a mutable reference to the `Face` for the provided index.
-    pub fn face_mut(&mut self, index: FaceIndex) -> Option<&mut Face> {
-        if index.is_valid() {
-            self.face_list.get_mut(index.0)
+    ///
+    /// Returns `None` for the default index as well as for indices which
+    /// are out of bounds or stale.
+    pub fn face_mut(&mut self, index: FaceIndex<Ix>) -> Option<&mut Face<F, Ix>> {
+        if index.is_valid() && self.is_current_face(index) {
+            self.face_list.get_mut(index.offset())
         } else {
             None
         }
     }
 
-    pub fn edge(&self, index: EdgeIndex) -> &Edge {
-        if let Some(result) = self.edge_list.get(index.0) {
-            result
+    /// Returns the index of the face currently stored at `offset` in
+    /// `face_list`.
+    pub fn face_index_at(&self, offset: usize) -> FaceIndex<Ix> {
+        FaceIndex(Ix::new(offset), self.face_slots.generation(offset))
+    }
+
+    /// Returns true when the index refers to a face of this mesh, and the
+    /// face has neither been removed nor moved since the index was
+    /// obtained.
+    pub fn is_current_face(&self, index: FaceIndex<Ix>) -> bool {
+        index.offset() < self.face_list.len() && index.1 == self.face_slots.generation(index.offset()) &&
+            !self.face_slots.is_dead(index.offset())
+    }
+
+    /// Returns the `Edge` for the provided index.
+    ///
+    /// Indices which are out of bounds or stale, referring to an edge
+    /// which has since been removed or moved, yield the default edge;
+    /// `Mesh::get_edge` tells them apart from current indices.
+    pub fn edge(&self, index: EdgeIndex<Ix>) -> &Edge<E, Ix> {
+        if self.is_current_edge(index) {
+            &self.edge_list[index.offset()]
         } else {
             &self.edge_list[0]
         }
     }
 
+    /// Returns the `Edge` for the provided index, or `None` for the default
+    /// index as well as for indices which are out of bounds or stale.
+    pub fn get_edge(&self, index: EdgeIndex<Ix>) -> Option<&Edge<E, Ix>> {
+        if index.is_valid() && self.is_current_edge(index) {
+            self.edge_list.get(index.offset())
+        } else {
+            None
+        }
+    }
+
     /// Returns an `EdgeFn` for the given index.
-    pub fn edge_fn(&self, index: EdgeIndex) -> EdgeFn {
-        EdgeFn::new(index, &self)
+    ///
+    /// Like `Mesh::edge`, a stale index yields a function set for the
+    /// default edge, which is not valid.
+    pub fn edge_fn(&self, index: EdgeIndex<Ix>) -> EdgeFn<'_, V, E, F, Ix> {
+        EdgeFn::new(index, self)
+    }
+
+    /// Returns an `EdgeFn` for the given index, or `None` when the index
+    /// isn't current.
+    pub fn get_edge_fn(&self, index: EdgeIndex<Ix>) -> Option<EdgeFn<'_, V, E, F, Ix>> {
+        self.get_edge(index).map(|_| EdgeFn::new(index, self))
     }
 
     /// Obtains a mutable reference to the `Edge` for the provided index.
-    pub fn edge_mut(&mut self, index: EdgeIndex) -> Option<&mut Edge> {
-        if index.is_valid() {
-            self.edge_list.get_mut(index.0)
+    ///
+    /// Returns `None` for the default index as well as for indices which
+    /// are out of bounds or stale.
+    pub fn edge_mut(&mut self, index: EdgeIndex<Ix>) -> Option<&mut Edge<E, Ix>> {
+        if index.is_valid() && self.is_current_edge(index) {
+            self.edge_list.get_mut(index.offset())
         } else {
             None
         }
     }
 
-    pub fn vertex(&self, index: VertexIndex) -> &Vertex {
-        if let Some(result) = self.vertex_list.get(index.0) {
-            result
+    /// Returns the index of the edge currently stored at `offset` in
+    /// `edge_list`.
+    pub fn edge_index_at(&self, offset: usize) -> EdgeIndex<Ix> {
+        EdgeIndex(Ix::new(offset), self.edge_slots.generation(offset))
+    }
+
+    /// Returns true when the index refers to a edge of this mesh, and the
+    /// edge has neither been removed nor moved since the index was
+    /// obtained.
+    pub fn is_current_edge(&self, index: EdgeIndex<Ix>) -> bool {
+        index.offset() < self.edge_list.len() && index.1 == self.edge_slots.generation(index.offset()) &&
+            !self.edge_slots.is_dead(index.offset())
+    }
+
+    /// Returns the `Vertex` for the provided index.
+    ///
+    /// Indices which are out of bounds or stale, referring to a vertex
+    /// which has since been removed or moved, yield the default vertex;
+    /// `Mesh::get_vertex` tells them apart from current indices.
+    pub fn vertex(&self, index: VertexIndex<Ix>) -> &Vertex<V, Ix> {
+        if self.is_current_vertex(index) {
+            &self.vertex_list[index.offset()]
         } else {
             &self.vertex_list[0]
         }
     }
 
+    /// Returns the `Vertex` for the provided index, or `None` for the default
+    /// index as well as for indices which are out of bounds or stale.
+    pub fn get_vertex(&self, index: VertexIndex<Ix>) -> Option<&Vertex<V, Ix>> {
+        if index.is_valid() && self.is_current_vertex(index) {
+            self.vertex_list.get(index.offset())
+        } else {
+            None
+        }
+    }
+
     /// Returns a `VertexFn` for the given index.
-    pub fn vertex_fn(&self, index: VertexIndex) -> VertexFn {
-        VertexFn::new(index, &self)
+    ///
+    /// Like `Mesh::vertex`, a stale index yields a function set for the
+    /// default vertex, which is not valid.
+    pub fn vertex_fn(&self, index: VertexIndex<Ix>) -> VertexFn<'_, V, E, F, Ix> {
+        VertexFn::new(index, self)
+    }
+
+    /// Returns a `VertexFn` for the given index, or `None` when the index
+    /// isn't current.
+    pub fn get_vertex_fn(&self, index: VertexIndex<Ix>) -> Option<VertexFn<'_, V, E, F, Ix>> {
+        self.get_vertex(index).map(|_| VertexFn::new(index, self))
+    }
+
+    /// Returns the position of the specified vertex.
+    ///
+    /// Vertices without a position are reported at the origin.
+    pub fn position(&self, index: VertexIndex<Ix>) -> &Position {
+        self.get_vertex(index)
+            .and_then(|vertex| self.position_list.get(vertex.attr_index))
+            .unwrap_or(&self.position_list[0])
     }
 
     /// Obtains a mutable reference to the `Vertex` for the provided index.
-    pub fn vertex_mut(&mut self, index: VertexIndex) -> Option<&mut Vertex> {
-        if index.is_valid() {
-            self.vertex_list.get_mut(index.0)
+    ///
+    /// Returns `None` for the default index as well as for indices which
+    /// are out of bounds or stale.
+    pub fn vertex_mut(&mut self, index: VertexIndex<Ix>) -> Option<&mut Vertex<V, Ix>> {
+        if index.is_valid() && self.is_current_vertex(index) {
+            self.vertex_list.get_mut(index.offset())
         } else {
             None
         }
     }
+
+    /// Returns the index of the vertex currently stored at `offset` in
+    /// `vertex_list`.
+    pub fn vertex_index_at(&self, offset: usize) -> VertexIndex<Ix> {
+        VertexIndex(Ix::new(offset), self.vertex_slots.generation(offset))
+    }
+
+    /// Returns true when the index refers to a vertex of this mesh, and the
+    /// vertex has neither been removed nor moved since the index was
+    /// obtained.
+    pub fn is_current_vertex(&self, index: VertexIndex<Ix>) -> bool {
+        index.offset() < self.vertex_list.len() && index.1 == self.vertex_slots.generation(index.offset()) &&
+            !self.vertex_slots.is_dead(index.offset())
+    }
 }
 
 /// An iterator that walks an edge loop around a face returning each `VertexIndex` in the loop.
 // yeah yeah yeah, I know this is copypasta...
-pub struct EdgeLoopVertices<'mesh> {
-    edge_list: &'mesh Vec<Edge>,
-    initial_index: EdgeIndex,
-    current_index: EdgeIndex
+pub struct EdgeLoopVertices<'mesh, E = (), Ix: IndexType = usize> {
+    edge_list: &'mesh Vec<Edge<E, Ix>>,
+    initial_index: EdgeIndex<Ix>,
+    current_index: EdgeIndex<Ix>
 }
 
-impl<'mesh> EdgeLoopVertices<'mesh> {
-    pub fn new(index: EdgeIndex, edge_list: &'mesh Vec<Edge>) -> EdgeLoopVertices {
+impl<'mesh, E, Ix: IndexType> EdgeLoopVertices<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> EdgeLoopVertices<'mesh, E, Ix> {
         EdgeLoopVertices {
-            edge_list: edge_list,
+            edge_list,
             initial_index: index,
             current_index: EdgeIndex::default()
         }
     }
 }
 
-impl<'mesh> Iterator for EdgeLoopVertices<'mesh> {
-    type Item = VertexIndex;
+impl<'mesh, E, Ix: IndexType> Iterator for EdgeLoopVertices<'mesh, E, Ix> {
+    type Item = VertexIndex<Ix>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current_index.is_valid() {
-            self.edge_list.get(self.current_index.0)
+            self.edge_list.get(self.current_index.offset())
                 .and_then(|last_edge| {
                     self.current_index = last_edge.next_index;
-                    if self.current_index == self.initial_index {
+                    if self.current_index == self.initial_index || !self.current_index.is_valid() {
                         None
                     } else {
-                        self.edge_list.get(self.current_index.0)
+                        self.edge_list.get(self.current_index.offset())
                             .map(|e| e.vertex_index)
                     }
                 })
-        } else {
+        } else if self.initial_index.is_valid() {
             self.current_index = self.initial_index;
-            self.edge_list.get(self.current_index.0).map(|e| e.vertex_index)
+            self.edge_list.get(self.current_index.offset()).map(|e| e.vertex_index)
+        } else {
+            None
         }
     }
 }
 
 /// An iterator that walks an edge loop around a face returning each `EdgeIndex` in the loop.
-pub struct EdgeLoop<'mesh> {
-    edge_list: &'mesh Vec<Edge>,
-    initial_index: EdgeIndex,
-    current_index: EdgeIndex
+pub struct EdgeLoop<'mesh, E = (), Ix: IndexType = usize> {
+    edge_list: &'mesh Vec<Edge<E, Ix>>,
+    initial_index: EdgeIndex<Ix>,
+    current_index: EdgeIndex<Ix>
 }
 
-impl<'mesh> EdgeLoop<'mesh> {
-    pub fn new(index: EdgeIndex, edge_list: &'mesh Vec<Edge>) -> EdgeLoop {
+impl<'mesh, E, Ix: IndexType> EdgeLoop<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> EdgeLoop<'mesh, E, Ix> {
         EdgeLoop {
-            edge_list: edge_list,
+            edge_list,
             initial_index: index,
             current_index: EdgeIndex::default()
         }
     }
 }
 
-impl<'mesh> Iterator for EdgeLoop<'mesh> {
-    type Item = EdgeIndex;
+impl<'mesh, E, Ix: IndexType> Iterator for EdgeLoop<'mesh, E, Ix> {
+    type Item = EdgeIndex<Ix>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current_index.is_valid() {
-            self.edge_list.get(self.current_index.0).and_then(|current_edge| {
+            self.edge_list.get(self.current_index.offset()).and_then(|current_edge| {
                 self.current_index = current_edge.next_index;
-                if self.current_index == self.initial_index {
+                if self.current_index == self.initial_index || !self.current_index.is_valid() {
                     None
                 } else {
                     Some(self.current_index)
                 }
             })
+        } else if self.initial_index.is_valid() {
+            self.current_index = self.initial_index;
+            Some(self.current_index)
         } else {
+            None
+        }
+    }
+}
+
+/// An iterator that circulates around a vertex returning the `EdgeIndex` of
+/// each edge starting at it.
+///
+/// Edges are visited by following `twin->next`. When the vertex is on a
+/// boundary we first rewind using `prev->twin` so that every edge is
+/// returned in order, starting with the edge that follows the boundary.
+pub struct OutgoingEdges<'mesh, E = (), Ix: IndexType = usize> {
+    edge_list: &'mesh Vec<Edge<E, Ix>>,
+    initial_index: EdgeIndex<Ix>,
+    current_index: EdgeIndex<Ix>,
+    remaining: usize,
+    is_closed: bool
+}
+
+impl<'mesh, E, Ix: IndexType> OutgoingEdges<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> OutgoingEdges<'mesh, E, Ix> {
+        OutgoingEdges {
+            edge_list,
+            initial_index: index,
+            current_index: EdgeIndex::default(),
+            remaining: edge_list.len(),
+            is_closed: false
+        }
+    }
+
+    fn edge(&self, index: EdgeIndex<Ix>) -> &'mesh Edge<E, Ix> {
+        self.edge_list.get(index.offset()).unwrap_or(&self.edge_list[0])
+    }
+
+    /// Finds the first edge after a boundary, or the initial edge when the
+    /// vertex is surrounded by faces.
+    fn rewind(&self) -> EdgeIndex<Ix> {
+        let mut result = self.initial_index;
+        for _ in 0 .. self.edge_list.len() {
+            let prev = self.edge(self.edge(result).prev_index).twin_index;
+            if !prev.is_valid() || prev == self.initial_index {
+                break;
+            }
+            result = prev;
+        }
+        result
+    }
+}
+
+impl<'mesh, E, Ix: IndexType> Iterator for OutgoingEdges<'mesh, E, Ix> {
+    type Item = EdgeIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        if self.current_index.is_valid() {
+            let next = self.edge(self.edge(self.current_index).twin_index).next_index;
+            if next == self.initial_index {
+                self.is_closed = true;
+                self.remaining = 0;
+                None
+            } else if next.is_valid() {
+                self.current_index = next;
+                Some(next)
+            } else {
+                self.remaining = 0;
+                None
+            }
+        } else if self.initial_index.is_valid() {
+            self.initial_index = self.rewind();
             self.current_index = self.initial_index;
             Some(self.current_index)
+        } else {
+            self.remaining = 0;
+            None
+        }
+    }
+}
+
+/// An iterator that circulates around a vertex returning the `EdgeIndex` of
+/// each edge ending at it.
+///
+/// These are the previous edges of the edges returned by `OutgoingEdges`.
+pub struct IncomingEdges<'mesh, E = (), Ix: IndexType = usize> {
+    outgoing: OutgoingEdges<'mesh, E, Ix>
+}
+
+impl<'mesh, E, Ix: IndexType> IncomingEdges<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> IncomingEdges<'mesh, E, Ix> {
+        IncomingEdges {
+            outgoing: OutgoingEdges::new(index, edge_list)
         }
     }
 }
 
-/// An iterator that returns the `FaceIndex` of every Face in the mesh.
+impl<'mesh, E, Ix: IndexType> Iterator for IncomingEdges<'mesh, E, Ix> {
+    type Item = EdgeIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(eindex) = self.outgoing.next() {
+            let prev = self.outgoing.edge(eindex).prev_index;
+            if prev.is_valid() {
+                return Some(prev);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator that circulates around a vertex returning the `VertexIndex`
+/// of each neighbouring vertex.
+///
+/// On a boundary the vertex at the start of the incoming boundary edge is
+/// returned last, since no outgoing edge leads to it.
+pub struct AdjacentVertices<'mesh, E = (), Ix: IndexType = usize> {
+    outgoing: OutgoingEdges<'mesh, E, Ix>,
+    first_index: EdgeIndex<Ix>
+}
+
+impl<'mesh, E, Ix: IndexType> AdjacentVertices<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> AdjacentVertices<'mesh, E, Ix> {
+        AdjacentVertices {
+            outgoing: OutgoingEdges::new(index, edge_list),
+            first_index: EdgeIndex::default()
+        }
+    }
+}
+
+impl<'mesh, E, Ix: IndexType> Iterator for AdjacentVertices<'mesh, E, Ix> {
+    type Item = VertexIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(eindex) = self.outgoing.next() {
+            if !self.first_index.is_valid() {
+                self.first_index = eindex;
+            }
+            let next = self.outgoing.edge(eindex).next_index;
+            let vindex = self.outgoing.edge(next).vertex_index;
+            if vindex.is_valid() {
+                return Some(vindex);
+            }
+        }
+        if self.first_index.is_valid() && !self.outgoing.is_closed {
+            let prev = self.outgoing.edge(self.first_index).prev_index;
+            self.first_index = EdgeIndex::default();
+            let vindex = self.outgoing.edge(prev).vertex_index;
+            if vindex.is_valid() {
+                return Some(vindex);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator that circulates around a vertex returning the `FaceIndex` of
+/// each face using it.
+pub struct IncidentFaces<'mesh, E = (), Ix: IndexType = usize> {
+    outgoing: OutgoingEdges<'mesh, E, Ix>
+}
+
+impl<'mesh, E, Ix: IndexType> IncidentFaces<'mesh, E, Ix> {
+    pub fn new(index: EdgeIndex<Ix>, edge_list: &'mesh Vec<Edge<E, Ix>>) -> IncidentFaces<'mesh, E, Ix> {
+        IncidentFaces {
+            outgoing: OutgoingEdges::new(index, edge_list)
+        }
+    }
+}
+
+impl<'mesh, E, Ix: IndexType> Iterator for IncidentFaces<'mesh, E, Ix> {
+    type Item = FaceIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(eindex) = self.outgoing.next() {
+            let findex = self.outgoing.edge(eindex).face_index;
+            if findex.is_valid() {
+                return Some(findex);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator that returns the `FaceIndex` of every Face in the mesh,
+/// skipping tombstones.
 ///
 /// Currently this does not iterate using connectivity information but will
 /// perhaps do this in the future.
-pub struct Faces {
-    face_count: usize,
-    previous_index: FaceIndex
+pub struct Faces<'mesh, V = (), E = (), F = (), Ix: IndexType = usize> {
+    mesh: &'mesh Mesh<V, E, F, Ix>,
+    previous_offset: usize
 }
 
-impl Faces {
-    pub fn new(face_count: usize) -> Faces {
+impl<'mesh, V, E, F, Ix: IndexType> Faces<'mesh, V, E, F, Ix> {
+    pub fn new(mesh: &'mesh Mesh<V, E, F, Ix>) -> Faces<'mesh, V, E, F, Ix> {
         Faces {
-            face_count: face_count,
-            previous_index: FaceIndex::default()
+            mesh,
+            previous_offset: INVALID_COMPONENT_INDEX
         }
     }
 }
 
 // TODO: iterate over faces based on connectivity?
-impl Iterator for Faces {
-    type Item = FaceIndex;
+impl<'mesh, V, E, F, Ix: IndexType> Iterator for Faces<'mesh, V, E, F, Ix> {
+    type Item = FaceIndex<Ix>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.previous_index = FaceIndex(self.previous_index.0 + 1);
-        if self.previous_index.0 >= self.face_count {
-            None
-        } else {
-            Some(self.previous_index)
+        loop {
+            self.previous_offset += 1;
+            if self.previous_offset >= self.mesh.face_list.len() {
+                return None;
+            }
+            if !self.mesh.face_slots.is_dead(self.previous_offset) {
+                return Some(self.mesh.face_index_at(self.previous_offset));
+            }
         }
     }
 }
@@ -0,0 +1,154 @@
+//!
+//! Face and vertex normals.
+//!
+
+use cgmath::{InnerSpace, Vector3};
+
+use super::*;
+
+
+/// The type of normals computed for faces and vertices.
+pub type Normal = Vector3<f32>;
+
+/// The name of the vertex attribute channel written by
+/// `Mesh::compute_vertex_normals`.
+pub const VERTEX_NORMAL_ATTRIBUTE: &str = "normal";
+
+/// How the normals of the faces around a vertex contribute to its normal.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NormalWeighting {
+    /// Every incident face contributes equally.
+    Uniform,
+    /// Faces contribute in proportion to their area.
+    Area,
+    /// Faces contribute in proportion to the angle of their corner at the
+    /// vertex.
+    Angle,
+}
+
+fn zero() -> Normal {
+    Normal::new(0.0, 0.0, 0.0)
+}
+
+/// Normalizes `v`, leaving degenerate vectors at zero instead of producing
+/// `NaN`s.
+fn normalize_or_zero(v: Normal) -> Normal {
+    let magnitude = v.magnitude();
+    if magnitude > 0.0 {
+        v / magnitude
+    } else {
+        zero()
+    }
+}
+
+impl<'mesh, V, E, F, Ix: IndexType> FaceFn<'mesh, V, E, F, Ix> {
+    /// Returns the sum of the edge cross products of this face, computed
+    /// with Newell's method. It points along the face normal and its length
+    /// is twice the area of the face, even for non-planar polygons.
+    fn newell_vector(&self) -> Normal {
+        let mut result = zero();
+        let mut positions = self.mesh.vertices(self.face)
+            .map(|vindex| self.mesh.position(vindex));
+        let first = match positions.next() {
+            Some(first) => first,
+            None => return result,
+        };
+        let mut current = first;
+        for next in positions.chain(Some(first)) {
+            result.x += (current.y - next.y) * (current.z + next.z);
+            result.y += (current.z - next.z) * (current.x + next.x);
+            result.z += (current.x - next.x) * (current.y + next.y);
+            current = next;
+        }
+        result
+    }
+
+    /// Returns the unit normal of this face, following the right hand rule
+    /// for the winding of its edge loop.
+    ///
+    /// Uses Newell's method, so polygons with more than three vertices are
+    /// handled as well. Degenerate faces have a zero normal.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Position};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex_at(Position::new(1.0, 0.0, 0.0));
+    /// let v3 = mesh.add_vertex_at(Position::new(0.0, 1.0, 0.0));
+    /// let f1 = mesh.add_triangle(v1, v2, v3);
+    ///
+    /// let normal = mesh.face_fn(f1).normal();
+    /// assert_eq!((normal.x, normal.y, normal.z), (0.0, 0.0, 1.0));
+    /// ```
+    pub fn normal(&self) -> Normal {
+        normalize_or_zero(self.newell_vector())
+    }
+
+    /// Returns the area of this face.
+    pub fn area(&self) -> f32 {
+        self.newell_vector().magnitude() * 0.5
+    }
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Computes a normal for every vertex by combining the normals of the
+    /// faces around it, and stores them in the `Normal` vertex attribute
+    /// channel named `VERTEX_NORMAL_ATTRIBUTE`.
+    ///
+    /// The channel is created when it doesn't exist yet, replacing any
+    /// channel of the same name with a different value type. Vertices
+    /// without incident faces get a zero normal.
+    pub fn compute_vertex_normals(&mut self, weighting: NormalWeighting) {
+        let face_vectors: Vec<Normal> = (0 .. self.face_list.len())
+            .map(|i| if i == INVALID_COMPONENT_INDEX {
+                zero()
+            } else {
+                self.face_fn(self.face_index_at(i)).newell_vector()
+            })
+            .collect();
+
+        let mut normals = vec![zero(); self.vertex_list.len()];
+        for (i, normal) in normals.iter_mut().enumerate().skip(1) {
+            let vertex = self.vertex_fn(self.vertex_index_at(i));
+            let origin = *vertex.position();
+            let mut sum = zero();
+            for eindex in vertex.outgoing_edges() {
+                let edge = self.edge(eindex);
+                if !edge.face_index.is_valid() {
+                    continue;
+                }
+                let face_vector = face_vectors[edge.face_index.offset()];
+                sum += match weighting {
+                    NormalWeighting::Uniform => normalize_or_zero(face_vector),
+                    // The Newell vector is already proportional to the area.
+                    NormalWeighting::Area => face_vector,
+                    NormalWeighting::Angle => {
+                        let edge = self.edge_fn(eindex);
+                        let to_next = *edge.next().vertex().position() - origin;
+                        let to_prev = *self.edge_fn(eindex).prev().vertex().position() - origin;
+                        if to_next.magnitude2() > 0.0 && to_prev.magnitude2() > 0.0 {
+                            normalize_or_zero(face_vector) * to_next.angle(to_prev).0
+                        } else {
+                            zero()
+                        }
+                    },
+                };
+            }
+            *normal = normalize_or_zero(sum);
+        }
+
+        if self.attribute::<Normal>(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE).is_none() {
+            self.remove_attribute(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE);
+            self.add_attribute_with_default(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE, zero());
+        }
+        let indices: Vec<VertexIndex<Ix>> = (0 .. normals.len()).map(|i| self.vertex_index_at(i)).collect();
+        if let Some(mut channel) = self.attribute_mut::<Normal>(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE) {
+            for (vindex, normal) in indices.into_iter().zip(normals) {
+                if let Some(value) = channel.get_mut(vindex) {
+                    *value = normal;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//!
+//! Reading and writing Wavefront OBJ files.
+//!
+//! Only available with the `obj` feature enabled.
+//!
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use cgmath::{Vector2, Vector3};
+
+use super::*;
+
+/// The name of the `Option<Vector2<f32>>` edge attribute channel holding the
+/// texture coordinate of the face corner each edge leaves.
+pub const TEX_COORD_ATTRIBUTE: &str = "uv";
+
+/// The name of the `Option<Vector3<f32>>` edge attribute channel holding the
+/// normal of the face corner each edge leaves.
+pub const CORNER_NORMAL_ATTRIBUTE: &str = "normal";
+
+/// The name of the `FaceTags` face attribute channel holding the object,
+/// group and material of each face.
+pub const FACE_TAGS_ATTRIBUTE: &str = "tags";
+
+/// Errors reported while reading an OBJ file.
+#[derive(Debug)]
+pub enum ObjError<Ix: IndexType = usize> {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// A line could not be parsed.
+    Parse { line: usize, message: String },
+    /// A vertex or face could not be added to the mesh.
+    Mesh { line: usize, error: Error<Ix> },
+}
+
+impl<Ix: IndexType> fmt::Display for ObjError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ObjError::Io(ref error) => write!(f, "{}", error),
+            ObjError::Parse { line, ref message } => write!(f, "line {}: {}", line, message),
+            ObjError::Mesh { line, ref error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl<Ix: IndexType> error::Error for ObjError<Ix> {}
+
+impl<Ix: IndexType> From<io::Error> for ObjError<Ix> {
+    fn from(error: io::Error) -> ObjError<Ix> {
+        ObjError::Io(error)
+    }
+}
+
+/// The object, group and material a face was declared with.
+///
+/// Each tag is an index into the corresponding list of names in `Obj`.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct FaceTags {
+    pub object: Option<usize>,
+    pub group: Option<usize>,
+    pub material: Option<usize>,
+}
+
+/// A mesh along with the names read from an OBJ file.
+///
+/// Per corner and per face data is kept in attribute channels of the mesh,
+/// so that it follows the components through edits.
+#[derive(Debug)]
+pub struct Obj<Ix: IndexType = usize> {
+    /// The mesh, with twin edges linked between adjacent faces, the
+    /// position of each vertex and, when the file declares any, the
+    /// `TEX_COORD_ATTRIBUTE` and `CORNER_NORMAL_ATTRIBUTE` edge channels
+    /// and the `FACE_TAGS_ATTRIBUTE` face channel.
+    pub mesh: Mesh<(), (), (), Ix>,
+    /// Names declared with `o`.
+    pub objects: Vec<String>,
+    /// Names declared with `g`.
+    pub groups: Vec<String>,
+    /// Names declared with `usemtl`.
+    pub materials: Vec<String>,
+}
+
+/// Reads an OBJ file into a `Mesh`.
+///
+/// Supports `v`, `vt`, `vn` and `f` statements, including polygons with any
+/// number of vertices and negative (relative) indices, as well as `o`, `g`
+/// and `usemtl` tags. Other statements are ignored.
+///
+/// Each `v` statement becomes a vertex of the mesh, so the n-th position in
+/// the file is `VertexIndex(n)`. Texture coordinates and normals of the face
+/// corners end up in the `TEX_COORD_ATTRIBUTE` and `CORNER_NORMAL_ATTRIBUTE`
+/// edge channels, on the edge leaving each corner, and the tags of each face
+/// in the `FACE_TAGS_ATTRIBUTE` face channel.
+///
+/// ```
+/// let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let obj = hedge::obj::read(source.as_bytes()).unwrap();
+///
+/// assert_eq!(obj.mesh.faces().count(), 1);
+/// ```
+pub fn read<R: BufRead>(reader: R) -> Result<Obj, ObjError> {
+    read_with_index_type(reader)
+}
+
+/// Reads an OBJ file into a `Mesh` with indices of type `Ix`, just like
+/// `read` does for `usize` indices.
+///
+/// ```
+/// let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let obj = hedge::obj::read_with_index_type::<u32, _>(source.as_bytes()).unwrap();
+///
+/// assert_eq!(obj.mesh.faces().count(), 1);
+/// ```
+pub fn read_with_index_type<Ix: IndexType, R: BufRead>(reader: R) -> Result<Obj<Ix>, ObjError<Ix>> {
+    let mut builder = MeshBuilder::default();
+    let mut result = Obj {
+        mesh: Mesh::default(),
+        objects: Vec::new(),
+        groups: Vec::new(),
+        materials: Vec::new(),
+    };
+    let mut tex_coord_list = Vec::new();
+    let mut normal_list = Vec::new();
+    let mut tex_coords = Vec::new();
+    let mut normals = Vec::new();
+    let mut face_tags = Vec::new();
+    let mut tags = FaceTags::default();
+    let mut corners = Vec::new();
+    let mut statement = String::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line?;
+        let line = line.split('#').next().unwrap_or("").trim_end();
+        if let Some(continued) = line.strip_suffix('\\') {
+            statement.push_str(continued);
+            statement.push(' ');
+            continue;
+        }
+        statement.push_str(line);
+
+        let parse_error = |message: String| ObjError::Parse { line: line_number, message };
+        {
+            let mut tokens = statement.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let p = parse_floats(&mut tokens, 3, 3).map_err(&parse_error)?;
+                    builder.try_add_vertex_at(Position::new(p[0], p[1], p[2]))
+                        .map_err(|error| ObjError::Mesh { line: line_number, error })?;
+                },
+                Some("vt") => {
+                    let t = parse_floats(&mut tokens, 1, 2).map_err(&parse_error)?;
+                    tex_coord_list.push(Vector2::new(t[0], t[1]));
+                },
+                Some("vn") => {
+                    let n = parse_floats(&mut tokens, 3, 3).map_err(&parse_error)?;
+                    normal_list.push(Vector3::new(n[0], n[1], n[2]));
+                },
+                Some("f") => {
+                    corners.clear();
+                    for token in tokens {
+                        let counts = (builder.mesh().vertex_list.len() - 1, tex_coord_list.len(), normal_list.len());
+                        corners.push(parse_corner(token, counts).map_err(&parse_error)?);
+                    }
+                    let verts: Vec<VertexIndex<Ix>> = corners.iter()
+                        .map(|c| builder.mesh().vertex_index_at(c.0 + 1))
+                        .collect();
+                    let findex = builder.add_polygon(&verts)
+                        .map_err(|error| ObjError::Mesh { line: line_number, error })?;
+
+                    let mesh = builder.mesh();
+                    face_tags.push((findex, tags));
+                    // The root edge of a new face always leaves the first vertex given
+                    for (eindex, corner) in mesh.edges(mesh.face(findex)).zip(corners.iter()) {
+                        if let Some(t) = corner.1 {
+                            tex_coords.push((eindex, Some(tex_coord_list[t])));
+                        }
+                        if let Some(n) = corner.2 {
+                            normals.push((eindex, Some(normal_list[n])));
+                        }
+                    }
+                },
+                Some("o") => {
+                    tags.object = Some(push_name(&mut result.objects, tokens));
+                },
+                Some("g") => {
+                    tags.group = Some(push_name(&mut result.groups, tokens));
+                },
+                Some("usemtl") => {
+                    tags.material = Some(push_name(&mut result.materials, tokens));
+                },
+                _ => {}
+            }
+        }
+        statement.clear();
+    }
+
+    result.mesh = builder.build();
+    if !tex_coord_list.is_empty() {
+        add_channel(&mut result.mesh, AttributeDomain::Edge, TEX_COORD_ATTRIBUTE, tex_coords);
+    }
+    if !normal_list.is_empty() {
+        add_channel(&mut result.mesh, AttributeDomain::Edge, CORNER_NORMAL_ATTRIBUTE, normals);
+    }
+    if !(result.objects.is_empty() && result.groups.is_empty() && result.materials.is_empty()) {
+        add_channel(&mut result.mesh, AttributeDomain::Face, FACE_TAGS_ATTRIBUTE, face_tags);
+    }
+    Ok(result)
+}
+
+/// Adds an attribute channel holding `values` for the given components and
+/// the default value for every other one.
+fn add_channel<T, I, Ix>(mesh: &mut Mesh<(), (), (), Ix>, domain: AttributeDomain, name: &str, values: Vec<(I, T)>)
+    where T: Clone + Default + 'static, I: ComponentIndex, Ix: IndexType
+{
+    mesh.add_attribute::<T>(domain, name);
+    if let Some(mut channel) = mesh.attribute_mut::<T>(domain, name) {
+        for (index, value) in values {
+            if let Some(slot) = channel.get_mut(index) {
+                *slot = value;
+            }
+        }
+    }
+}
+
+/// Parses between `min` and `max` floats, filling in missing values with zero.
+fn parse_floats<'a, I>(tokens: &mut I, min: usize, max: usize) -> Result<[f32; 3], String>
+    where I: Iterator<Item = &'a str>
+{
+    let mut result = [0.0; 3];
+    let mut count = 0;
+    for token in tokens.take(max) {
+        result[count] = token.parse().map_err(|_| format!("invalid number '{}'", token))?;
+        count += 1;
+    }
+    if count < min {
+        return Err(format!("expected at least {} values", min));
+    }
+    Ok(result)
+}
+
+/// Parses a face corner of the form `v`, `v/vt`, `v//vn` or `v/vt/vn`
+/// into zero based indices.
+///
+/// `counts` holds the number of positions, texture coordinates and normals
+/// declared so far, used to resolve negative indices.
+fn parse_corner(token: &str, counts: (usize, usize, usize))
+                -> Result<(usize, Option<usize>, Option<usize>), String> {
+    let mut parts = token.split('/');
+    let position = match parts.next() {
+        Some(part) if !part.is_empty() => resolve_index(part, counts.0)?,
+        _ => return Err(format!("missing vertex index in '{}'", token)),
+    };
+    let tex_coord = match parts.next() {
+        Some(part) if !part.is_empty() => Some(resolve_index(part, counts.1)?),
+        _ => None,
+    };
+    let normal = match parts.next() {
+        Some(part) if !part.is_empty() => Some(resolve_index(part, counts.2)?),
+        _ => None,
+    };
+    Ok((position, tex_coord, normal))
+}
+
+/// Converts a one based, possibly negative, OBJ index into a zero based index.
+fn resolve_index(token: &str, count: usize) -> Result<usize, String> {
+    let index: i64 = token.parse().map_err(|_| format!("invalid index '{}'", token))?;
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    if resolved < 0 || resolved >= count as i64 {
+        return Err(format!("index {} is out of range", index));
+    }
+    Ok(resolved as usize)
+}
+
+/// Adds the name made up of the remaining tokens, returning its index.
+fn push_name<'a, I>(names: &mut Vec<String>, tokens: I) -> usize
+    where I: Iterator<Item = &'a str>
+{
+    let name = tokens.collect::<Vec<_>>().join(" ");
+    match names.iter().position(|n| *n == name) {
+        Some(index) => index,
+        None => {
+            names.push(name);
+            names.len() - 1
+        }
+    }
+}
+
+/// Writes the mesh and attributes of `obj` as an OBJ file.
+///
+/// Every vertex is written as a position, so `VertexIndex(n)` becomes the
+/// n-th vertex of the file. Tombstones left by `RemovalMode::Tombstone` are
+/// skipped, and the vertices after them are numbered as if the mesh had
+/// been compacted with `Mesh::compact`.
+///
+/// Texture coordinates and normals are written for the face corners that
+/// have them in the `TEX_COORD_ATTRIBUTE` and `CORNER_NORMAL_ATTRIBUTE`
+/// edge channels, sharing identical values between corners. `o`, `g` and
+/// `usemtl` statements are emitted whenever the tags in the
+/// `FACE_TAGS_ATTRIBUTE` face channel change from one face to the next.
+///
+/// ```
+/// let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let obj = hedge::obj::read(source.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// hedge::obj::write(&mut output, &obj).unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(), source);
+/// ```
+pub fn write<Ix: IndexType, W: Write>(writer: &mut W, obj: &Obj<Ix>) -> io::Result<()> {
+    let mesh = &obj.mesh;
+    // Dead slots left by tombstone removal are skipped, so the written
+    // vertices are renumbered by their position in the file.
+    let mut vertex_ids = vec![0; mesh.vertex_list.len()];
+    let mut vertex_count = 0;
+    for (offset, id) in vertex_ids.iter_mut().enumerate().skip(1) {
+        let vindex = mesh.vertex_index_at(offset);
+        if !mesh.is_current_vertex(vindex) {
+            continue;
+        }
+        vertex_count += 1;
+        *id = vertex_count;
+        let p = mesh.position(vindex);
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+
+    let tex_coords = mesh.attribute::<Option<Vector2<f32>>>(AttributeDomain::Edge, TEX_COORD_ATTRIBUTE);
+    let normals = mesh.attribute::<Option<Vector3<f32>>>(AttributeDomain::Edge, CORNER_NORMAL_ATTRIBUTE);
+    let face_tags = mesh.attribute::<FaceTags>(AttributeDomain::Face, FACE_TAGS_ATTRIBUTE);
+    let tex_coord_at = |eindex| tex_coords.as_ref().and_then(|channel| channel.get(eindex)).cloned().unwrap_or(None);
+    let normal_at = |eindex| normals.as_ref().and_then(|channel| channel.get(eindex)).cloned().unwrap_or(None);
+
+    let mut tex_coord_ids = HashMap::new();
+    let mut normal_ids = HashMap::new();
+    for findex in mesh.faces() {
+        for eindex in mesh.edges(mesh.face(findex)) {
+            if let Some(t) = tex_coord_at(eindex) {
+                let key = (t.x.to_bits(), t.y.to_bits());
+                if !tex_coord_ids.contains_key(&key) {
+                    tex_coord_ids.insert(key, tex_coord_ids.len() + 1);
+                    writeln!(writer, "vt {} {}", t.x, t.y)?;
+                }
+            }
+            if let Some(n) = normal_at(eindex) {
+                let key = (n.x.to_bits(), n.y.to_bits(), n.z.to_bits());
+                if !normal_ids.contains_key(&key) {
+                    normal_ids.insert(key, normal_ids.len() + 1);
+                    writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+                }
+            }
+        }
+    }
+
+    let mut current_tags = FaceTags::default();
+    for findex in mesh.faces() {
+        let face = mesh.face(findex);
+        if !face.edge_index.is_valid() {
+            continue;
+        }
+
+        let tags = face_tags.as_ref().and_then(|channel| channel.get(findex)).cloned().unwrap_or_default();
+        if tags.object != current_tags.object {
+            write_name(writer, "o", &obj.objects, tags.object)?;
+        }
+        if tags.group != current_tags.group {
+            write_name(writer, "g", &obj.groups, tags.group)?;
+        }
+        if tags.material != current_tags.material {
+            write_name(writer, "usemtl", &obj.materials, tags.material)?;
+        }
+        current_tags = tags;
+
+        write!(writer, "f")?;
+        for (vindex, eindex) in mesh.vertices(face).zip(mesh.edges(face)) {
+            write!(writer, " {}", vertex_ids[vindex.offset()])?;
+            let tex_coord = tex_coord_at(eindex).map(|t| tex_coord_ids[&(t.x.to_bits(), t.y.to_bits())]);
+            let normal = normal_at(eindex).map(|n| normal_ids[&(n.x.to_bits(), n.y.to_bits(), n.z.to_bits())]);
+            match (tex_coord, normal) {
+                (Some(t), Some(n)) => write!(writer, "/{}/{}", t, n)?,
+                (Some(t), None) => write!(writer, "/{}", t)?,
+                (None, Some(n)) => write!(writer, "//{}", n)?,
+                (None, None) => {}
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes a tag statement, falling back to `default` for faces without one.
+fn write_name<W: Write>(writer: &mut W, keyword: &str, names: &[String], index: Option<usize>) -> io::Result<()> {
+    let name = index.and_then(|i| names.get(i)).map_or("default", |n| n.as_str());
+    writeln!(writer, "{} {}", keyword, name)
+}
@@ -0,0 +1,693 @@
+//!
+//! Local topological operators.
+//!
+//! Unlike the low level methods of `Mesh`, which modify single components
+//! and leave it to the caller to restore connectivity, each operator here
+//! takes a valid mesh to another valid mesh.
+//!
+
+use std::collections::HashSet;
+
+use super::*;
+
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Returns the edge running from `from` to `to`, if there is one.
+    pub fn find_edge(&self, from: VertexIndex<Ix>, to: VertexIndex<Ix>) -> Option<EdgeIndex<Ix>> {
+        self.get_vertex_fn(from)?.outgoing_edges()
+            .find(|&eindex| self.edge_fn(eindex).next().vertex().index == to)
+    }
+
+    /// Returns the three edges of the triangle starting at `index`, or
+    /// `Error::NonTriangularFace` when the loop has a different length.
+    fn triangle_edges(&self, index: EdgeIndex<Ix>) -> Result<[EdgeIndex<Ix>; 3], Error<Ix>> {
+        let e0 = self.edge(index);
+        let e1 = self.edge(e0.next_index);
+        if !e0.next_index.is_valid() || !e1.next_index.is_valid() ||
+            self.edge(e1.next_index).next_index != index {
+                return Err(Error::NonTriangularFace(e0.face_index));
+            }
+        Ok([index, e0.next_index, e1.next_index])
+    }
+
+    /// Links `edges` into a closed loop belonging to `face`, making the
+    /// first edge the root of the face.
+    fn link_loop(&mut self, face: FaceIndex<Ix>, edges: &[EdgeIndex<Ix>]) {
+        for (i, &eindex) in edges.iter().enumerate() {
+            let next_index = edges[(i + 1) % edges.len()];
+            self.edge_list[eindex.offset()].next_index = next_index;
+            self.edge_list[next_index.offset()].prev_index = eindex;
+            self.edge_list[eindex.offset()].face_index = face;
+        }
+        self.face_list[face.offset()].edge_index = edges[0];
+    }
+
+    /// Rotates an interior edge shared by two triangles so that it connects
+    /// the two vertices opposite to it.
+    ///
+    /// Both half-edges keep their indices, as do the two faces. Boundary
+    /// edges, edges of other polygons and flips which would duplicate an
+    /// existing edge are refused without modifying the mesh.
+    ///
+    /// Edge attributes describe the corner of a face at the vertex an edge
+    /// starts from. The flipped half-edges take theirs from the edges which
+    /// started at the same vertex of the same face before the flip, so
+    /// per-corner values such as texture coordinates stay at their corners.
+    ///
+    /// ```
+    /// use hedge::{MeshBuilder, Vertex};
+    /// let mut builder = MeshBuilder::new();
+    ///
+    /// let v1 = builder.add_vertex(Vertex::default());
+    /// let v2 = builder.add_vertex(Vertex::default());
+    /// let v3 = builder.add_vertex(Vertex::default());
+    /// let v4 = builder.add_vertex(Vertex::default());
+    /// let f1 = builder.add_polygon(&[v1, v2, v3]).unwrap();
+    /// builder.add_polygon(&[v2, v1, v4]).unwrap();
+    /// let mut mesh = builder.build();
+    ///
+    /// let diagonal = mesh.find_edge(v1, v2).unwrap();
+    /// mesh.flip_edge(diagonal).unwrap();
+    /// assert_eq!(mesh.edge_fn(diagonal).vertex().index, v3);
+    /// assert_eq!(mesh.edge_fn(diagonal).next().vertex().index, v4);
+    /// assert_eq!(mesh.edge(diagonal).face_index, f1);
+    /// ```
+    pub fn flip_edge(&mut self, index: EdgeIndex<Ix>) -> Result<(), Error<Ix>> {
+        self.check_edge_index(index)?;
+        let twin_index = self.edge(index).twin_index;
+        if !twin_index.is_valid() {
+            return Err(Error::BoundaryEdge(index));
+        }
+        let face = self.edge(index).face_index;
+        let twin_face = self.edge(twin_index).face_index;
+        self.check_face_index(face)?;
+        self.check_face_index(twin_face)?;
+
+        // Before: a -e-> b -e1-> c -e2-> a and b -t-> a -t1-> d -t2-> b
+        let [e, e1, e2] = self.triangle_edges(index)?;
+        let [t, t1, t2] = self.triangle_edges(twin_index)?;
+        let a = self.edge(e).vertex_index;
+        let b = self.edge(t).vertex_index;
+        let c = self.edge(e2).vertex_index;
+        let d = self.edge(t2).vertex_index;
+        if c == d || self.find_edge(c, d).is_some() || self.find_edge(d, c).is_some() {
+            return Err(Error::DuplicateEdge(c, d));
+        }
+
+        // After: c -e-> d -t2-> b -e1-> c and d -t-> c -e2-> a -t1-> d
+        self.edge_list[e.offset()].vertex_index = c;
+        self.edge_list[t.offset()].vertex_index = d;
+        self.link_loop(face, &[e, t2, e1]);
+        self.link_loop(twin_face, &[t, e2, t1]);
+        self.attributes.copy(AttributeDomain::Edge, e.offset(), e2.offset());
+        self.attributes.copy(AttributeDomain::Edge, t.offset(), t2.offset());
+
+        if self.vertex(a).edge_index == e {
+            self.vertex_list[a.offset()].edge_index = t1;
+        }
+        if self.vertex(b).edge_index == t {
+            self.vertex_list[b.offset()].edge_index = e1;
+        }
+        Ok(())
+    }
+
+    /// Inserts a new vertex on an edge, splitting the edge and its twin in
+    /// two and returning the index of the new vertex.
+    ///
+    /// The new vertex is placed at `t` along the edge, running from the
+    /// vertex of the edge at `0.0` to the vertex of its next edge at `1.0`.
+    /// Its position is interpolated linearly, while the values of attribute
+    /// channels are derived according to each channel's `Interpolation`:
+    /// vertex values between the two endpoints, edge values between the
+    /// corners at either end of the edge. Undirected edge values are copied.
+    ///
+    /// The faces on either side gain a vertex, so splitting an edge of a
+    /// triangle leaves a quad; see `split_edge_triangulated`.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Position};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex_at(Position::new(2.0, 0.0, 0.0));
+    /// let v3 = mesh.add_vertex_at(Position::new(0.0, 2.0, 0.0));
+    /// let f1 = mesh.add_triangle(v1, v2, v3);
+    ///
+    /// let edge = mesh.find_edge(v1, v2).unwrap();
+    /// let v4 = mesh.split_edge(edge, 0.25).unwrap();
+    /// assert_eq!(*mesh.position(v4), Position::new(0.5, 0.0, 0.0));
+    /// assert_eq!(mesh.vertices(mesh.face(f1)).count(), 4);
+    /// ```
+    pub fn split_edge(&mut self, index: EdgeIndex<Ix>, t: f32) -> Result<VertexIndex<Ix>, Error<Ix>>
+        where V: Default, E: Default
+    {
+        self.check_edge_index(index)?;
+        let next_index = self.edge(index).next_index;
+        if !next_index.is_valid() || !self.edge(index).prev_index.is_valid() {
+            return Err(Error::DisconnectedEdge(index));
+        }
+        let twin_index = self.edge(index).twin_index;
+        if twin_index.is_valid() && !self.edge(twin_index).is_connected() {
+            return Err(Error::DisconnectedEdge(twin_index));
+        }
+
+        // Before: a -e-> b and b -twin-> a
+        let a = self.edge(index).vertex_index;
+        let b = self.edge(next_index).vertex_index;
+        let position = *self.position(a) + (*self.position(b) - *self.position(a)) * t;
+        let middle = self.add_vertex_at(position);
+        self.attributes.interpolate(AttributeDomain::Vertex, middle.offset(), a.offset(), b.offset(), t);
+
+        // After: a -e-> middle -split-> b and b -twin-> middle -twin_split-> a
+        let split = self.insert_edge_after(index, middle);
+        self.attributes.interpolate(AttributeDomain::Edge, split.offset(), index.offset(), next_index.offset(), t);
+        self.vertex_list[middle.offset()].edge_index = split;
+
+        if twin_index.is_valid() {
+            let twin_next = self.edge(twin_index).next_index;
+            let twin_split = self.insert_edge_after(twin_index, middle);
+            self.attributes.interpolate(AttributeDomain::Edge, twin_split.offset(), twin_index.offset(), twin_next.offset(), 1.0 - t);
+            self.edge_list[index.offset()].twin_index = twin_split;
+            self.edge_list[twin_split.offset()].twin_index = index;
+            self.edge_list[split.offset()].twin_index = twin_index;
+            self.edge_list[twin_index.offset()].twin_index = split;
+        }
+
+        Ok(middle)
+    }
+
+    /// Splits an edge like `split_edge`, then splits each adjacent face which
+    /// was a triangle into two triangles by connecting the new vertex to the
+    /// opposite corner.
+    pub fn split_edge_triangulated(&mut self, index: EdgeIndex<Ix>, t: f32) -> Result<VertexIndex<Ix>, Error<Ix>>
+        where V: Default, E: Default, F: Default
+    {
+        self.check_edge_index(index)?;
+        let twin_index = self.edge(index).twin_index;
+        let is_triangle = |mesh: &Mesh<V, E, F, Ix>, eindex: EdgeIndex<Ix>| {
+            eindex.is_valid() && mesh.edge(eindex).face_index.is_valid() &&
+                mesh.triangle_edges(eindex).is_ok()
+        };
+        let triangles = [
+            (index, is_triangle(self, index)),
+            (twin_index, is_triangle(self, twin_index)),
+        ];
+
+        let middle = self.split_edge(index, t)?;
+        for &(eindex, is_triangle) in &triangles {
+            if is_triangle {
+                // eindex now ends at the new vertex, followed by the edge
+                // leaving it, and preceded by the edge leaving the corner
+                // opposite to it.
+                let from = self.edge(eindex).next_index;
+                let to = self.edge(eindex).prev_index;
+                self.insert_diagonal(from, to);
+            }
+        }
+        Ok(middle)
+    }
+
+    /// Splits a face in two by connecting two of its vertices which aren't
+    /// already connected, returning the index of the new face.
+    ///
+    /// The part of the loop running from `a` to `b` becomes the new face,
+    /// whose attribute values are copied from the original one. The rest
+    /// of the loop keeps the original face.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Vertex};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex(Vertex::default());
+    /// let v2 = mesh.add_vertex(Vertex::default());
+    /// let v3 = mesh.add_vertex(Vertex::default());
+    /// let v4 = mesh.add_vertex(Vertex::default());
+    /// let f1 = mesh.add_polygon(&[v1, v2, v3, v4]);
+    ///
+    /// let f2 = mesh.split_face(f1, v1, v3).unwrap();
+    /// assert_eq!(mesh.vertices(mesh.face(f1)).count(), 3);
+    /// assert_eq!(mesh.vertices(mesh.face(f2)).count(), 3);
+    /// ```
+    pub fn split_face(&mut self, index: FaceIndex<Ix>, a: VertexIndex<Ix>, b: VertexIndex<Ix>) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.check_face_index(index)?;
+        self.check_vertex_index(a)?;
+        self.check_vertex_index(b)?;
+        let root = self.face(index).edge_index;
+        self.check_edge_index(root)?;
+        if !self.loop_is_closed(root) {
+            return Err(Error::OpenEdgeLoop(root));
+        }
+        if a == b {
+            return Err(Error::DegeneratePolygon);
+        }
+
+        let find_corner = |vindex: VertexIndex<Ix>| {
+            self.edges(self.face(index))
+                .find(|&eindex| self.edge(eindex).vertex_index == vindex)
+                .ok_or(Error::VertexNotInFace(vindex, index))
+        };
+        let from = find_corner(a)?;
+        let to = find_corner(b)?;
+        if self.find_edge(a, b).is_some() || self.find_edge(b, a).is_some() {
+            return Err(Error::DuplicateEdge(a, b));
+        }
+
+        let (new_face, _) = self.insert_diagonal(from, to);
+        Ok(new_face)
+    }
+
+    /// Adds a new edge starting at `vertex` to the loop of `index`, directly
+    /// after it. The new edge takes over the end of `index`.
+    fn insert_edge_after(&mut self, index: EdgeIndex<Ix>, vertex: VertexIndex<Ix>) -> EdgeIndex<Ix>
+        where E: Default
+    {
+        let next_index = self.edge(index).next_index;
+        let result = self.add_edge(Edge {
+            twin_index: EdgeIndex::default(),
+            next_index,
+            prev_index: index,
+            face_index: self.edge(index).face_index,
+            vertex_index: vertex,
+            data: E::default(),
+        });
+        self.attributes.interpolate(AttributeDomain::UndirectedEdge, result.offset(), index.offset(), index.offset(), 0.0);
+        self.edge_list[index.offset()].next_index = result;
+        self.edge_list[next_index.offset()].prev_index = result;
+        result
+    }
+
+    /// Splits the face of the loop containing `from` and `to` in two by
+    /// connecting the vertices they start at with a pair of twin edges.
+    ///
+    /// The loop from `to` up to `from` keeps the original face, while the
+    /// loop from `from` up to `to` gets a new face whose attribute values
+    /// are copied from the original one, and whose edges are assigned with
+    /// `assign_face_to_loop`. Returns the new face and the edge of the pair
+    /// which belongs to it.
+    pub(crate) fn insert_diagonal(&mut self, from: EdgeIndex<Ix>, to: EdgeIndex<Ix>) -> (FaceIndex<Ix>, EdgeIndex<Ix>)
+        where E: Default, F: Default
+    {
+        let face = self.edge(from).face_index;
+        let p = self.edge(from).vertex_index;
+        let q = self.edge(to).vertex_index;
+        let from_prev = self.edge(from).prev_index;
+        let to_prev = self.edge(to).prev_index;
+
+        let forward = self.add_edge(Edge {
+            twin_index: EdgeIndex::default(),
+            next_index: to,
+            prev_index: from_prev,
+            face_index: face,
+            vertex_index: p,
+            data: E::default(),
+        });
+        let backward = self.add_edge(Edge {
+            twin_index: forward,
+            next_index: from,
+            prev_index: to_prev,
+            face_index: face,
+            vertex_index: q,
+            data: E::default(),
+        });
+        self.edge_list[forward.offset()].twin_index = backward;
+        self.edge_list[from_prev.offset()].next_index = forward;
+        self.edge_list[to.offset()].prev_index = forward;
+        self.edge_list[to_prev.offset()].next_index = backward;
+        self.edge_list[from.offset()].prev_index = backward;
+        // Both new edges sit at existing corners
+        self.attributes.interpolate(AttributeDomain::Edge, forward.offset(), from.offset(), from.offset(), 0.0);
+        self.attributes.interpolate(AttributeDomain::Edge, backward.offset(), to.offset(), to.offset(), 0.0);
+
+        if face.is_valid() {
+            self.face_list[face.offset()].edge_index = to;
+        }
+        let new_face = self.add_face(Face::new(from));
+        self.attributes.interpolate(AttributeDomain::Face, new_face.offset(), face.offset(), face.offset(), 0.0);
+        self.assign_face_to_loop(new_face, from);
+        (new_face, backward)
+    }
+
+    /// Merges the two vertices of an edge into one at `target_position`,
+    /// returning the index of the merged vertex.
+    ///
+    /// Adjacent triangles collapse into single edges and are removed along
+    /// with the edge, linking the twins on either side of each of them.
+    /// Adjacent faces with more edges lose one edge instead. The merged
+    /// vertex keeps the attribute values of the vertex of the edge, and the
+    /// two edges which become one where a triangle collapses keep the
+    /// undirected edge attribute values of the one at that vertex.
+    ///
+    /// The collapse is refused with `Error::LinkCondition` when it would
+    /// make the mesh non-manifold, that is when the two vertices have
+    /// neighbours in common other than the corners opposite to the edge, or
+    /// when the edge is interior but connects two boundary vertices.
+    ///
+    /// Components are removed as with the removal methods, so in the default
+    /// `RemovalMode::SwapRemove` indices of the last components in each list
+    /// are reused.
+    pub fn collapse_edge(&mut self, index: EdgeIndex<Ix>, target_position: Position) -> Result<VertexIndex<Ix>, Error<Ix>>
+        where V: Default, E: Default, F: Default
+    {
+        self.check_edge_index(index)?;
+        if !self.edge(index).is_connected() {
+            return Err(Error::DisconnectedEdge(index));
+        }
+        let twin_index = self.edge(index).twin_index;
+        if twin_index.is_valid() && !self.edge(twin_index).is_connected() {
+            return Err(Error::DisconnectedEdge(twin_index));
+        }
+        let a = self.edge(index).vertex_index;
+        let b = self.edge_fn(index).next().vertex().index;
+
+        // The triangles on either side of the edge, as edge triples
+        let mut triangles = Vec::new();
+        let mut polygon_sides = Vec::new();
+        for &eindex in &[index, twin_index] {
+            if !eindex.is_valid() {
+                continue;
+            }
+            match self.triangle_edges(eindex) {
+                Ok(edges) if self.edge(eindex).face_index.is_valid() => triangles.push(edges),
+                _ => polygon_sides.push(eindex),
+            }
+        }
+        if !self.satisfies_link_condition(index, &triangles) {
+            return Err(Error::LinkCondition(index));
+        }
+
+        let mut removed_edges = polygon_sides.clone();
+        let mut removed_faces = Vec::new();
+        // Edges which might have to stand in for removed ones as the edge of
+        // their vertex, gathered while the mesh can still be circulated.
+        let mut neighbourhood: Vec<EdgeIndex<Ix>> = self.vertex_fn(a).outgoing_edges()
+            .chain(self.vertex_fn(b).outgoing_edges())
+            .collect();
+        for &[_, _, e2] in &triangles {
+            let opposite = self.edge(e2).vertex_index;
+            neighbourhood.extend(self.vertex_fn(opposite).outgoing_edges());
+        }
+
+        for &[e0, e1, e2] in &triangles {
+            // e1 and e2 become the same edge, so their twins become twins.
+            let (t1, t2) = (self.edge(e1).twin_index, self.edge(e2).twin_index);
+            if let Some(edge) = self.edge_mut(t1) {
+                edge.twin_index = t2;
+            }
+            if let Some(edge) = self.edge_mut(t2) {
+                edge.twin_index = t1;
+            }
+            // The side at the merged vertex keeps its undirected edge values.
+            let (kept, merged) = if self.edge(e1).vertex_index == a { (t1, t2) } else { (t2, t1) };
+            if kept.is_valid() && merged.is_valid() {
+                self.attributes.copy(AttributeDomain::UndirectedEdge, merged.offset(), kept.offset());
+            }
+            removed_edges.extend(&[e0, e1, e2]);
+            removed_faces.push(self.edge(e0).face_index);
+        }
+        for &eindex in &polygon_sides {
+            self.unlink_edge(eindex);
+        }
+
+        for edge in &mut self.edge_list {
+            if edge.vertex_index == b {
+                edge.vertex_index = a;
+            }
+        }
+        self.vertex_list[b.offset()].edge_index = EdgeIndex::default();
+        for eindex in neighbourhood {
+            if removed_edges.contains(&eindex) {
+                continue;
+            }
+            let vindex = self.edge(eindex).vertex_index;
+            let vertex = &mut self.vertex_list[vindex.offset()];
+            if !vertex.edge_index.is_valid() || removed_edges.contains(&vertex.edge_index) {
+                vertex.edge_index = eindex;
+            }
+        }
+        self.set_position(a, target_position);
+
+        self.remove_detached(removed_faces, removed_edges);
+        self.remove_vertex(b, RemovalPolicy::Refuse)?;
+        Ok(if self.is_current_vertex(a) { a } else { self.vertex_index_at(b.offset()) })
+    }
+
+    /// Removes an interior edge and its twin, merging the faces on either
+    /// side into one and returning its index.
+    ///
+    /// The merged face keeps the lower index of the two, along with its
+    /// attribute values. Edges with the same face on both sides are refused
+    /// with `Error::BridgeEdge`.
+    ///
+    /// ```
+    /// use hedge::{MeshBuilder, Vertex};
+    /// let mut builder = MeshBuilder::new();
+    ///
+    /// let v1 = builder.add_vertex(Vertex::default());
+    /// let v2 = builder.add_vertex(Vertex::default());
+    /// let v3 = builder.add_vertex(Vertex::default());
+    /// let v4 = builder.add_vertex(Vertex::default());
+    /// builder.add_polygon(&[v1, v2, v3]).unwrap();
+    /// builder.add_polygon(&[v2, v1, v4]).unwrap();
+    /// let mut mesh = builder.build();
+    ///
+    /// let diagonal = mesh.find_edge(v1, v2).unwrap();
+    /// let quad = mesh.dissolve_edge(diagonal).unwrap();
+    /// assert_eq!(mesh.faces().count(), 1);
+    /// assert_eq!(mesh.vertices(mesh.face(quad)).count(), 4);
+    /// ```
+    pub fn dissolve_edge(&mut self, index: EdgeIndex<Ix>) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.check_edge_index(index)?;
+        let twin_index = self.edge(index).twin_index;
+        if !twin_index.is_valid() {
+            return Err(Error::BoundaryEdge(index));
+        }
+        for &eindex in &[index, twin_index] {
+            if !self.edge(eindex).is_connected() {
+                return Err(Error::DisconnectedEdge(eindex));
+            }
+            self.check_face_index(self.edge(eindex).face_index)?;
+        }
+        let face = self.edge(index).face_index;
+        let twin_face = self.edge(twin_index).face_index;
+        if face == twin_face {
+            return Err(Error::BridgeEdge(index));
+        }
+        let (kept, removed) = if face.offset() < twin_face.offset() {
+            (face, twin_face)
+        } else {
+            (twin_face, face)
+        };
+
+        // Before: x -prev-> a -e-> b -next-> y and y' -twin_prev-> b -twin-> a -twin_next-> x'
+        let Edge { next_index, prev_index, vertex_index: a, .. } = *self.edge(index);
+        let Edge { next_index: twin_next, prev_index: twin_prev, vertex_index: b, .. } = *self.edge(twin_index);
+        self.link_edges(prev_index, twin_next);
+        self.link_edges(twin_prev, next_index);
+        self.assign_face_to_loop(kept, next_index);
+        if self.vertex(a).edge_index == index {
+            self.vertex_list[a.offset()].edge_index = twin_next;
+        }
+        if self.vertex(b).edge_index == twin_index {
+            self.vertex_list[b.offset()].edge_index = next_index;
+        }
+
+        self.remove_detached(vec![removed], vec![index, twin_index]);
+        Ok(kept)
+    }
+
+    /// Removes a vertex along with all of its edges, merging the faces
+    /// around it into one and returning its index.
+    ///
+    /// The merged face keeps the lowest index of the faces, along with its
+    /// attribute values. When the vertex is on a boundary a new boundary
+    /// edge closes the merged face, so dissolving the corner of a polygon
+    /// cuts it off.
+    pub fn dissolve_vertex(&mut self, index: VertexIndex<Ix>) -> Result<FaceIndex<Ix>, Error<Ix>>
+        where V: Default, E: Default, F: Default
+    {
+        self.check_vertex_index(index)?;
+        let outgoing: Vec<EdgeIndex<Ix>> = self.vertex_fn(index).outgoing_edges().collect();
+        if outgoing.is_empty() {
+            return Err(Error::IsolatedVertex(index));
+        }
+        let mut faces = Vec::new();
+        for &eindex in &outgoing {
+            let edge = self.edge(eindex);
+            if !edge.is_connected() {
+                return Err(Error::DisconnectedEdge(eindex));
+            }
+            self.check_face_index(edge.face_index)?;
+            if faces.contains(&edge.face_index) {
+                return Err(Error::BridgeEdge(eindex));
+            }
+            faces.push(edge.face_index);
+        }
+
+        // Circulation starts right after the boundary, if there is one.
+        let first = outgoing[0];
+        let last = outgoing[outgoing.len() - 1];
+        let first_incoming = self.edge(first).prev_index;
+        let is_boundary = self.edge(first_incoming).is_boundary();
+        let loop_len = faces.iter()
+            .map(|&findex| self.edges(self.face(findex)).count() - 2)
+            .sum::<usize>();
+        if loop_len + (is_boundary as usize) < 3 {
+            return Err(Error::DegeneratePolygon);
+        }
+
+        let mut neighbourhood = Vec::new();
+        let mut removed_edges = Vec::new();
+        let mut links = Vec::new();
+        for &eindex in &outgoing {
+            // Each face contributes the edges between the incoming and
+            // outgoing edge of the vertex, continued by the face across
+            // the incoming edge.
+            let incoming = self.edge(eindex).prev_index;
+            let twin = self.edge(incoming).twin_index;
+            if twin.is_valid() {
+                links.push((self.edge(incoming).prev_index, self.edge(twin).next_index));
+            }
+            let neighbour = self.edge(incoming).vertex_index;
+            neighbourhood.extend(self.vertex_fn(neighbour).outgoing_edges());
+            let neighbour = self.edge_fn(eindex).next().vertex().index;
+            neighbourhood.extend(self.vertex_fn(neighbour).outgoing_edges());
+            removed_edges.push(eindex);
+            removed_edges.push(incoming);
+        }
+        for (from, to) in links {
+            self.link_edges(from, to);
+        }
+        if is_boundary {
+            let from = self.edge(first_incoming).prev_index;
+            let to = self.edge(last).next_index;
+            let bridge = self.add_edge(Edge {
+                vertex_index: self.edge(first_incoming).vertex_index,
+                .. Edge::default()
+            });
+            self.attributes.interpolate(AttributeDomain::Edge, bridge.offset(), first_incoming.offset(), first_incoming.offset(), 0.0);
+            self.attributes.interpolate(AttributeDomain::UndirectedEdge, bridge.offset(), first_incoming.offset(), last.offset(), 0.5);
+            self.link_edges(from, bridge);
+            self.link_edges(bridge, to);
+            neighbourhood.push(bridge);
+        }
+
+        let kept = faces.iter().cloned().min_by_key(|f| f.offset()).unwrap_or_default();
+        let root = self.edge(first).next_index;
+        self.assign_face_to_loop(kept, root);
+        self.vertex_list[index.offset()].edge_index = EdgeIndex::default();
+        for eindex in neighbourhood {
+            if removed_edges.contains(&eindex) {
+                continue;
+            }
+            let vindex = self.edge(eindex).vertex_index;
+            let vertex = &mut self.vertex_list[vindex.offset()];
+            if removed_edges.contains(&vertex.edge_index) {
+                vertex.edge_index = eindex;
+            }
+        }
+
+        faces.retain(|&findex| findex != kept);
+        self.remove_detached(faces, removed_edges);
+        self.remove_vertex(index, RemovalPolicy::Refuse)?;
+        Ok(kept)
+    }
+
+    /// Removes a face along with its edges, leaving a hole in the mesh.
+    ///
+    /// The twins of the removed edges become boundary edges. Vertices which
+    /// were only connected to the removed face are kept but left without an
+    /// edge.
+    pub fn dissolve_face(&mut self, index: FaceIndex<Ix>) -> Result<(), Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.check_face_index(index)?;
+        let root = self.face(index).edge_index;
+        self.check_edge_index(root)?;
+        if !self.loop_is_closed(root) {
+            return Err(Error::OpenEdgeLoop(root));
+        }
+
+        let edges: Vec<EdgeIndex<Ix>> = self.edges(self.face(index)).collect();
+        let mut neighbourhood = Vec::new();
+        for &eindex in &edges {
+            let vindex = self.edge(eindex).vertex_index;
+            neighbourhood.extend(self.vertex_fn(vindex).outgoing_edges());
+        }
+        for &eindex in &edges {
+            let twin_index = self.edge(eindex).twin_index;
+            if let Some(twin) = self.edge_mut(twin_index) {
+                twin.twin_index = EdgeIndex::default();
+            }
+        }
+        for eindex in neighbourhood {
+            if edges.contains(&eindex) {
+                continue;
+            }
+            let vindex = self.edge(eindex).vertex_index;
+            let vertex = &mut self.vertex_list[vindex.offset()];
+            if edges.contains(&vertex.edge_index) {
+                vertex.edge_index = eindex;
+            }
+        }
+
+        self.remove_detached(vec![index], edges);
+        Ok(())
+    }
+
+    /// Checks whether collapsing the edge keeps the mesh manifold, given the
+    /// triangles on either side of it.
+    fn satisfies_link_condition(&self, index: EdgeIndex<Ix>, triangles: &[[EdgeIndex<Ix>; 3]]) -> bool {
+        let a = self.edge(index).vertex_index;
+        let b = self.edge_fn(index).next().vertex().index;
+        let (vertex_a, vertex_b) = (self.vertex_fn(a), self.vertex_fn(b));
+        if !self.edge(index).is_boundary() && vertex_a.is_boundary() && vertex_b.is_boundary() {
+            return false;
+        }
+
+        let opposite: HashSet<VertexIndex<Ix>> = triangles.iter()
+            .map(|&[_, _, e2]| self.edge(e2).vertex_index)
+            .collect();
+        let neighbours_a: HashSet<VertexIndex<Ix>> = vertex_a.adjacent_vertices().collect();
+        let common = vertex_b.adjacent_vertices()
+            .filter(|v| *v != a && *v != b && neighbours_a.contains(v))
+            .collect::<HashSet<VertexIndex<Ix>>>();
+        if common != opposite {
+            return false;
+        }
+
+        // The edges opposite to each vertex in its triangles, which would
+        // end up with three faces if both vertices share one.
+        let opposite_edges = |vindex: VertexIndex<Ix>, other: VertexIndex<Ix>| {
+            self.vertex_fn(vindex).outgoing_edges()
+                .filter(|&eindex| self.edge(eindex).face_index.is_valid())
+                .filter_map(|eindex| self.triangle_edges(eindex).ok())
+                .map(|[_, e1, e2]| (self.edge(e1).vertex_index, self.edge(e2).vertex_index))
+                .filter(|&(v1, v2)| v1 != other && v2 != other)
+                .map(|(v1, v2)| if v1.0 < v2.0 { (v1, v2) } else { (v2, v1) })
+                .collect::<HashSet<_>>()
+        };
+        opposite_edges(a, b).is_disjoint(&opposite_edges(b, a))
+    }
+
+    /// Makes `to` the next edge of `from`.
+    fn link_edges(&mut self, from: EdgeIndex<Ix>, to: EdgeIndex<Ix>) {
+        self.edge_list[from.offset()].next_index = to;
+        self.edge_list[to.offset()].prev_index = from;
+    }
+
+    /// Removes an edge from its loop, joining its previous and next edges.
+    fn unlink_edge(&mut self, index: EdgeIndex<Ix>) {
+        let Edge { next_index, prev_index, face_index, .. } = *self.edge(index);
+        self.edge_list[prev_index.offset()].next_index = next_index;
+        self.edge_list[next_index.offset()].prev_index = prev_index;
+        if let Some(face) = self.face_mut(face_index) {
+            if face.edge_index == index {
+                face.edge_index = next_index;
+            }
+        }
+    }
+}
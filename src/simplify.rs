@@ -0,0 +1,307 @@
+//!
+//! Mesh simplification.
+//!
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use cgmath::{EuclideanSpace, InnerSpace, Matrix3, SquareMatrix, Vector3};
+
+use super::*;
+
+
+/// The weight of the planes which keep boundary edges in place, relative to
+/// the planes of faces.
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+
+/// A symmetric 4x4 matrix measuring the sum of squared distances of a point
+/// to a set of planes.
+#[derive(Debug, Default, Clone, Copy)]
+struct Quadric {
+    // a², ab, ac, ad, b², bc, bd, c², cd, d²
+    m: [f64; 10],
+}
+
+impl Quadric {
+    /// The quadric of the plane `normal · p + d = 0`, scaled by `weight`.
+    fn from_plane(normal: Vector3<f64>, d: f64, weight: f64) -> Quadric {
+        let (a, b, c) = (normal.x, normal.y, normal.z);
+        let mut m = [a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d];
+        for value in &mut m {
+            *value *= weight;
+        }
+        Quadric { m }
+    }
+
+    fn add(&self, other: &Quadric) -> Quadric {
+        let mut m = self.m;
+        for (value, other) in m.iter_mut().zip(other.m.iter()) {
+            *value += *other;
+        }
+        Quadric { m }
+    }
+
+    fn error(&self, p: Vector3<f64>) -> f64 {
+        let m = &self.m;
+        m[0] * p.x * p.x + 2.0 * m[1] * p.x * p.y + 2.0 * m[2] * p.x * p.z + 2.0 * m[3] * p.x +
+            m[4] * p.y * p.y + 2.0 * m[5] * p.y * p.z + 2.0 * m[6] * p.y +
+            m[7] * p.z * p.z + 2.0 * m[8] * p.z +
+            m[9]
+    }
+
+    /// The point with the smallest error, if it is well defined.
+    fn minimum(&self) -> Option<Vector3<f64>> {
+        let m = &self.m;
+        let a = Matrix3::new(
+            m[0], m[1], m[2],
+            m[1], m[4], m[5],
+            m[2], m[5], m[7],
+        );
+        if a.determinant().abs() < 1e-12 {
+            return None;
+        }
+        a.invert().map(|inverse| -(inverse * Vector3::new(m[3], m[6], m[8])))
+    }
+}
+
+/// A pending collapse of the edge between two vertices.
+///
+/// Vertices are tracked by index together with a generation, which changes
+/// whenever a collapse modifies the vertex or moves it to another index, so
+/// that outdated candidates can be skipped.
+struct Candidate<Ix: IndexType = usize> {
+    cost: f64,
+    vertices: (VertexIndex<Ix>, VertexIndex<Ix>),
+    generations: (usize, usize),
+    target: Vector3<f64>,
+}
+
+impl<Ix: IndexType> PartialEq for Candidate<Ix> {
+    fn eq(&self, other: &Candidate<Ix>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Ix: IndexType> Eq for Candidate<Ix> {}
+
+impl<Ix: IndexType> PartialOrd for Candidate<Ix> {
+    fn partial_cmp(&self, other: &Candidate<Ix>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Ix: IndexType> Ord for Candidate<Ix> {
+    /// Reversed, so that `BinaryHeap` pops the cheapest candidate first.
+    fn cmp(&self, other: &Candidate<Ix>) -> Ordering {
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+    }
+}
+
+fn point<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) -> Vector3<f64> {
+    mesh.position(index).to_vec().cast()
+}
+
+/// The unnormalized normal of a polygon, using Newell's method.
+fn newell(points: &[Vector3<f64>]) -> Vector3<f64> {
+    let mut result = Vector3::new(0.0, 0.0, 0.0);
+    for (i, current) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+        result.x += (current.y - next.y) * (current.z + next.z);
+        result.y += (current.z - next.z) * (current.x + next.x);
+        result.z += (current.x - next.x) * (current.y + next.y);
+    }
+    result
+}
+
+/// Computes the quadric of every vertex from the planes of the faces around
+/// it, along with planes perpendicular to the faces along boundary edges.
+fn vertex_quadrics<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> Vec<Quadric> {
+    let mut result = vec![Quadric::default(); mesh.vertex_list.len()];
+    for findex in mesh.faces() {
+        let verts: Vec<VertexIndex<Ix>> = mesh.vertices(mesh.face(findex)).collect();
+        let points: Vec<Vector3<f64>> = verts.iter().map(|&v| point(mesh, v)).collect();
+        let normal = newell(&points);
+        if normal.magnitude2() == 0.0 {
+            continue;
+        }
+        let normal = normal.normalize();
+        let quadric = Quadric::from_plane(normal, -normal.dot(points[0]), 1.0);
+        for &vindex in &verts {
+            result[vindex.offset()] = result[vindex.offset()].add(&quadric);
+        }
+
+        for eindex in mesh.edges(mesh.face(findex)) {
+            if !mesh.edge(eindex).is_boundary() {
+                continue;
+            }
+            let from = mesh.edge(eindex).vertex_index;
+            let to = mesh.edge_fn(eindex).next().vertex().index;
+            let side = (point(mesh, to) - point(mesh, from)).cross(normal);
+            if side.magnitude2() == 0.0 {
+                continue;
+            }
+            let side = side.normalize();
+            let quadric = Quadric::from_plane(side, -side.dot(point(mesh, from)), BOUNDARY_WEIGHT);
+            result[from.offset()] = result[from.offset()].add(&quadric);
+            result[to.offset()] = result[to.offset()].add(&quadric);
+        }
+    }
+    result
+}
+
+/// Returns true when moving both vertices to `target` would turn any of the
+/// faces around them over, or collapse one of them to nothing. Faces
+/// containing both vertices are left out, since they lose an edge.
+fn flips_faces<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, a: VertexIndex<Ix>, b: VertexIndex<Ix>, target: Vector3<f64>) -> bool {
+    for &vindex in &[a, b] {
+        for findex in mesh.vertex_fn(vindex).incident_faces() {
+            let verts: Vec<VertexIndex<Ix>> = mesh.vertices(mesh.face(findex)).collect();
+            if verts.contains(&a) && verts.contains(&b) {
+                continue;
+            }
+            let before: Vec<Vector3<f64>> = verts.iter().map(|&v| point(mesh, v)).collect();
+            let after: Vec<Vector3<f64>> = verts.iter()
+                .map(|&v| if v == vindex { target } else { point(mesh, v) })
+                .collect();
+            if newell(&before).dot(newell(&after)) <= 0.0 {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+struct Decimation<Ix: IndexType = usize> {
+    quadrics: Vec<Quadric>,
+    generations: Vec<usize>,
+    next_generation: usize,
+    queue: BinaryHeap<Candidate<Ix>>,
+}
+
+impl<Ix: IndexType> Decimation<Ix> {
+    fn push<V, E, F>(&mut self, mesh: &Mesh<V, E, F, Ix>, a: VertexIndex<Ix>, b: VertexIndex<Ix>) {
+        let quadric = self.quadrics[a.offset()].add(&self.quadrics[b.offset()]);
+        let (pa, pb) = (point(mesh, a), point(mesh, b));
+        let target = quadric.minimum().unwrap_or_else(|| {
+            let mid = (pa + pb) * 0.5;
+            *[pa, pb, mid].iter()
+                .min_by(|x, y| quadric.error(**x).partial_cmp(&quadric.error(**y)).unwrap_or(Ordering::Equal))
+                .unwrap_or(&mid)
+        });
+        self.queue.push(Candidate {
+            cost: quadric.error(target),
+            vertices: (a, b),
+            generations: (self.generations[a.offset()], self.generations[b.offset()]),
+            target,
+        });
+    }
+
+    fn push_neighbourhood<V, E, F>(&mut self, mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) {
+        let neighbours: Vec<VertexIndex<Ix>> = mesh.vertex_fn(index).adjacent_vertices().collect();
+        for neighbour in neighbours {
+            self.push(mesh, index, neighbour);
+        }
+    }
+
+    fn renew(&mut self, index: VertexIndex<Ix>) {
+        self.next_generation += 1;
+        self.generations[index.offset()] = self.next_generation;
+    }
+
+    fn is_current(&self, candidate: &Candidate<Ix>) -> bool {
+        let ((a, b), (ga, gb)) = (candidate.vertices, candidate.generations);
+        a.offset() < self.generations.len() && b.offset() < self.generations.len() &&
+            self.generations[a.offset()] == ga && self.generations[b.offset()] == gb
+    }
+}
+
+/// Reduces the number of faces of a mesh to `target_face_count` by
+/// collapsing edges, returning the number of faces left.
+///
+/// Edges are collapsed in order of their quadric error, as described by
+/// Garland and Heckbert, with each pair of vertices moved to the position
+/// which minimizes the squared distance to the planes of their original
+/// faces. Planes perpendicular to boundary edges keep boundaries in place.
+/// Collapses which would turn faces over or make the mesh non-manifold are
+/// skipped, so fewer faces may be removed than requested. Since collapsing
+/// an interior edge removes two faces, one more face than requested may be
+/// removed as well.
+///
+/// Like `Mesh::collapse_edge`, this reuses the indices of removed
+/// components.
+///
+/// ```
+/// use hedge::{simplify, subdivide, MeshBuilder, Position};
+/// let mut builder = MeshBuilder::new();
+/// let v1 = builder.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+/// let v2 = builder.add_vertex_at(Position::new(1.0, 0.0, 0.0));
+/// let v3 = builder.add_vertex_at(Position::new(0.0, 1.0, 0.0));
+/// builder.add_polygon(&[v1, v2, v3]).unwrap();
+/// let mut mesh = subdivide::loop_subdivision(&builder.build(), 2).unwrap();
+///
+/// let face_count = simplify::decimate(&mut mesh, 8);
+/// assert!(face_count <= 8);
+/// assert_eq!(mesh.faces().count(), face_count);
+/// ```
+pub fn decimate<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &mut Mesh<V, E, F, Ix>, target_face_count: usize) -> usize {
+    let quadrics = vertex_quadrics(mesh);
+    let mut decimation = Decimation {
+        generations: vec![0; quadrics.len()],
+        quadrics,
+        next_generation: 0,
+        queue: BinaryHeap::new(),
+    };
+    for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+        if edge.twin_index.is_valid() && edge.twin_index.offset() < i {
+            continue;
+        }
+        let to = mesh.edge_fn(mesh.edge_index_at(i)).next().vertex().index;
+        if edge.vertex_index.is_valid() && to.is_valid() {
+            decimation.push(mesh, edge.vertex_index, to);
+        }
+    }
+
+    while mesh.face_count() > target_face_count {
+        let candidate = match decimation.queue.pop() {
+            Some(candidate) => candidate,
+            None => break,
+        };
+        if !decimation.is_current(&candidate) {
+            continue;
+        }
+        let (a, b) = candidate.vertices;
+        let eindex = match mesh.find_edge(a, b).or_else(|| mesh.find_edge(b, a)) {
+            Some(eindex) => eindex,
+            None => continue,
+        };
+        if flips_faces(mesh, a, b, candidate.target) {
+            continue;
+        }
+
+        let quadric = decimation.quadrics[a.offset()].add(&decimation.quadrics[b.offset()]);
+        let origin = mesh.edge(eindex).vertex_index;
+        let removed = if origin == a { b } else { a };
+        let target = candidate.target.cast();
+        let vertex_slots = mesh.vertex_list.len();
+        let merged = match mesh.collapse_edge(eindex, Position::from_vec(target)) {
+            Ok(merged) => merged,
+            Err(_) => continue,
+        };
+
+        if mesh.vertex_list.len() < vertex_slots {
+            // Mirror the `swap_remove` of the removed vertex
+            decimation.quadrics.swap_remove(removed.offset());
+            decimation.generations.swap_remove(removed.offset());
+        }
+        decimation.quadrics[merged.offset()] = quadric;
+        decimation.renew(merged);
+        decimation.push_neighbourhood(mesh, merged);
+        let moved = mesh.vertex_index_at(removed.offset());
+        if mesh.is_current_vertex(moved) && moved != merged {
+            decimation.renew(moved);
+            decimation.push_neighbourhood(mesh, moved);
+        }
+    }
+
+    mesh.face_count()
+}
@@ -0,0 +1,200 @@
+//!
+//! Smoothing of vertex positions.
+//!
+//! Every pass moves each vertex towards a weighted average of its one-ring,
+//! the vertices sharing an edge with it. New positions are computed from the
+//! positions of the previous pass, so the result doesn't depend on the
+//! order of the vertices.
+//!
+
+use cgmath::{EuclideanSpace, InnerSpace, Vector3};
+
+use super::*;
+
+
+/// How the neighbours of a vertex are weighted in its Laplacian.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LaplacianWeights {
+    /// Every neighbour contributes equally.
+    Uniform,
+    /// Neighbours contribute by the cotangents of the angles opposite the
+    /// edge to them, which keeps triangles from sliding along a smooth
+    /// surface. Only triangles contribute; vertices without any triangles
+    /// around them use uniform weights.
+    Cotangent,
+}
+
+/// Settings shared by the smoothing functions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Smoothing<'a> {
+    /// The weights of the neighbours of each vertex.
+    pub weights: LaplacianWeights,
+    /// The number of passes to apply.
+    pub iterations: usize,
+    /// Keeps vertices on a boundary edge in place, so that open meshes
+    /// don't shrink away from their outline.
+    pub pin_boundary: bool,
+    /// The name of a vertex channel of `bool`s selecting the vertices which
+    /// may be moved. Every vertex is selected when this is `None`.
+    pub selection: Option<&'a str>,
+}
+
+impl<'a> Default for Smoothing<'a> {
+    fn default() -> Smoothing<'a> {
+        Smoothing {
+            weights: LaplacianWeights::Uniform,
+            iterations: 1,
+            pin_boundary: true,
+            selection: None,
+        }
+    }
+}
+
+fn offset<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) -> Vector3<f32> {
+    mesh.position(index).to_vec()
+}
+
+/// The cotangent of the angle at `corner` between the directions to `a`
+/// and `b`.
+fn cotangent(corner: Vector3<f32>, a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    let (u, v) = (a - corner, b - corner);
+    let sine = u.cross(v).magnitude();
+    if sine > 0.0 { u.dot(v) / sine } else { 0.0 }
+}
+
+/// Returns the neighbours of a vertex along with their cotangent weights.
+fn cotangent_weights<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) -> Vec<(VertexIndex<Ix>, f32)> {
+    let mut result: Vec<(VertexIndex<Ix>, f32)> = Vec::new();
+    let mut add = |vindex: VertexIndex<Ix>, weight: f32| {
+        match result.iter_mut().find(|entry| entry.0 == vindex) {
+            Some(entry) => entry.1 += weight,
+            None => result.push((vindex, weight)),
+        }
+    };
+    let p = offset(mesh, index);
+    for eindex in mesh.vertex_fn(index).outgoing_edges() {
+        let edge = mesh.edge(eindex);
+        let next = mesh.edge(edge.next_index);
+        if !edge.face_index.is_valid() || next.next_index != edge.prev_index {
+            continue;
+        }
+        // The triangle (index, j, k) weights the edge to j by the angle at
+        // k and the edge to k by the angle at j
+        let (j, k) = (next.vertex_index, mesh.edge(edge.prev_index).vertex_index);
+        let (pj, pk) = (offset(mesh, j), offset(mesh, k));
+        add(j, 0.5 * cotangent(pk, p, pj));
+        add(k, 0.5 * cotangent(pj, p, pk));
+    }
+    result
+}
+
+/// Returns the weighted average of the neighbours of a vertex minus its
+/// position, or `None` for isolated vertices.
+fn laplacian_of<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>, weights: LaplacianWeights) -> Option<Vector3<f32>> {
+    let p = offset(mesh, index);
+    if weights == LaplacianWeights::Cotangent {
+        let neighbours = cotangent_weights(mesh, index);
+        let total: f32 = neighbours.iter().map(|&(_, w)| w).sum();
+        if total > 0.0 {
+            let sum = neighbours.iter()
+                .fold(Vector3::new(0.0, 0.0, 0.0), |sum, &(v, w)| sum + (offset(mesh, v) - p) * w);
+            return Some(sum / total);
+        }
+    }
+
+    let mut count = 0;
+    let mut sum = Vector3::new(0.0, 0.0, 0.0);
+    for vindex in mesh.vertex_fn(index).adjacent_vertices() {
+        sum += offset(mesh, vindex) - p;
+        count += 1;
+    }
+    if count > 0 { Some(sum / count as f32) } else { None }
+}
+
+/// Returns whether each vertex may be moved, or `Error::UnknownAttribute`
+/// when the selection channel is missing or of another type.
+fn movable<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, options: &Smoothing) -> Result<Vec<bool>, Error<Ix>> {
+    let selection = match options.selection {
+        Some(name) => Some(mesh.attribute::<bool>(AttributeDomain::Vertex, name)
+                           .ok_or(Error::UnknownAttribute)?),
+        None => None,
+    };
+    let result = (0 .. mesh.vertex_list.len())
+        .map(|i| {
+            let index = mesh.vertex_index_at(i);
+            let selected = match selection {
+                Some(selection) => selection.get(index) == Some(&true),
+                None => true,
+            };
+            index.is_valid() && selected &&
+                !(options.pin_boundary && mesh.vertex_fn(index).is_boundary())
+        })
+        .collect();
+    Ok(result)
+}
+
+/// Moves every movable vertex by `factor` times its Laplacian.
+fn step<V, E, F, Ix: IndexType>(mesh: &mut Mesh<V, E, F, Ix>, movable: &[bool], weights: LaplacianWeights, factor: f32) {
+    let positions: Vec<(VertexIndex<Ix>, Position)> = (1 .. mesh.vertex_list.len())
+        .map(|i| mesh.vertex_index_at(i))
+        .filter(|index| movable[index.offset()])
+        .filter_map(|index| {
+            laplacian_of(mesh, index, weights)
+                .map(|laplacian| (index, *mesh.position(index) + laplacian * factor))
+        })
+        .collect();
+    for (index, position) in positions {
+        mesh.set_position(index, position);
+    }
+}
+
+/// Smooths a mesh by moving each vertex a fraction `lambda` of the way
+/// towards the weighted average of its neighbours, once per iteration.
+///
+/// Vertices are only moved when they are selected by `options.selection`
+/// and aren't pinned to the boundary. Returns `Error::UnknownAttribute`,
+/// without moving any vertex, when the mesh has no vertex channel of
+/// `bool`s by the name of the selection.
+///
+/// ```
+/// use hedge::{MeshBuilder, Position};
+/// use hedge::smooth::{self, Smoothing};
+/// let mut builder = MeshBuilder::new();
+/// let v1 = builder.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+/// let v2 = builder.add_vertex_at(Position::new(3.0, 0.0, 0.0));
+/// let v3 = builder.add_vertex_at(Position::new(0.0, 3.0, 0.0));
+/// let v4 = builder.add_vertex_at(Position::new(1.0, 1.0, 1.0));
+/// builder.add_polygon(&[v1, v2, v4]).unwrap();
+/// builder.add_polygon(&[v2, v3, v4]).unwrap();
+/// builder.add_polygon(&[v3, v1, v4]).unwrap();
+/// let mut mesh = builder.build();
+///
+/// smooth::laplacian(&mut mesh, 1.0, Smoothing::default()).unwrap();
+/// assert_eq!(*mesh.position(v4), Position::new(1.0, 1.0, 0.0));
+/// assert_eq!(*mesh.position(v1), Position::new(0.0, 0.0, 0.0));
+/// ```
+pub fn laplacian<V, E, F, Ix: IndexType>(mesh: &mut Mesh<V, E, F, Ix>, lambda: f32, options: Smoothing) -> Result<(), Error<Ix>> {
+    let movable = movable(mesh, &options)?;
+    for _ in 0 .. options.iterations {
+        step(mesh, &movable, options.weights, lambda);
+    }
+    Ok(())
+}
+
+/// Smooths a mesh without shrinking it, as described by Taubin.
+///
+/// Each iteration applies a Laplacian pass with the positive factor
+/// `lambda`, followed by one with the negative factor `mu` which pushes
+/// vertices back out. `mu` should be slightly larger in magnitude than
+/// `lambda`, for example `lambda = 0.5` and `mu = -0.53`.
+///
+/// Vertices are selected and pinned the same way as by `laplacian`, which
+/// also reports the same errors.
+pub fn taubin<V, E, F, Ix: IndexType>(mesh: &mut Mesh<V, E, F, Ix>, lambda: f32, mu: f32, options: Smoothing) -> Result<(), Error<Ix>> {
+    let movable = movable(mesh, &options)?;
+    for _ in 0 .. options.iterations {
+        step(mesh, &movable, options.weights, lambda);
+        step(mesh, &movable, options.weights, mu);
+    }
+    Ok(())
+}
@@ -0,0 +1,381 @@
+//!
+//! Subdivision surfaces.
+//!
+//! Each scheme takes a mesh and returns a new, finer mesh with twins linked
+//! by a `MeshBuilder`. Only positions are carried over; attribute channels
+//! of the input mesh are not, and every component of the result holds the
+//! default payload.
+//!
+
+use std::f32::consts::PI;
+
+use cgmath::{EuclideanSpace, Vector3};
+
+use super::*;
+
+
+/// Applies `step` to the mesh `iterations` times.
+fn repeat<V: Default, E: Default, F: Default, Ix: IndexType, S>(mesh: &Mesh<V, E, F, Ix>, iterations: usize, step: S) -> Result<Mesh<V, E, F, Ix>, Error<Ix>>
+    where S: Fn(&Mesh<V, E, F, Ix>) -> Result<Mesh<V, E, F, Ix>, Error<Ix>>
+{
+    let mut result = None;
+    for _ in 0 .. iterations {
+        let next = step(result.as_ref().unwrap_or(mesh))?;
+        result = Some(next);
+    }
+    match result {
+        Some(result) => Ok(result),
+        None => rebuild(mesh),
+    }
+}
+
+/// Copies the vertices and faces of a mesh into a new mesh.
+fn rebuild<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+    let mut builder = MeshBuilder::default();
+    let mut vertex_points = vec![VertexIndex::default(); mesh.vertex_list.len()];
+    for (i, point) in vertex_points.iter_mut().enumerate().skip(1) {
+        let index = mesh.vertex_index_at(i);
+        if mesh.is_current_vertex(index) {
+            *point = builder.try_add_vertex_at(*mesh.position(index))?;
+        }
+    }
+    for findex in mesh.faces() {
+        let verts: Vec<VertexIndex<Ix>> = mesh.vertices(mesh.face(findex))
+            .map(|v| vertex_points[v.offset()])
+            .collect();
+        builder.add_polygon(&verts)?;
+    }
+    Ok(builder.build())
+}
+
+/// Assigns a point index to every undirected edge, shared by both halves of
+/// twin edges, skipping tombstones. Returns the index of each half-edge's
+/// point and the number of points.
+fn edge_points<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> (Vec<usize>, usize) {
+    let mut result = vec![0; mesh.edge_list.len()];
+    let mut count = 0;
+    for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+        let twin = edge.twin_index;
+        if !mesh.is_current_edge(mesh.edge_index_at(i)) {
+            continue;
+        } else if twin.is_valid() && twin.offset() < i {
+            result[i] = result[twin.offset()];
+        } else {
+            result[i] = count;
+            count += 1;
+        }
+    }
+    (result, count)
+}
+
+/// Returns the neighbours of a vertex which share a boundary edge with it.
+fn boundary_neighbours<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) -> Vec<VertexIndex<Ix>> {
+    let mut result = Vec::new();
+    for eindex in mesh.vertex_fn(index).outgoing_edges() {
+        let edge = mesh.edge(eindex);
+        if edge.is_boundary() {
+            result.push(mesh.edge_fn(eindex).next().vertex().index);
+        }
+        if mesh.edge(edge.prev_index).is_boundary() {
+            result.push(mesh.edge_fn(eindex).prev().vertex().index);
+        }
+    }
+    result
+}
+
+fn offset<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, index: VertexIndex<Ix>) -> Vector3<f32> {
+    mesh.position(index).to_vec()
+}
+
+/// Subdivides a triangle mesh `iterations` times using Loop's scheme.
+///
+/// Every triangle is split into four, and positions are computed with the
+/// Loop stencils. Along boundaries, vertices and new edge points only take
+/// their boundary neighbours into account, so boundary curves are
+/// subdivided as cubic B-splines.
+///
+/// Faces other than triangles are reported as `Error::NonTriangularFace`.
+///
+/// ```
+/// use hedge::subdivide;
+/// let tri: &[usize] = &[0, 1, 2];
+/// let mesh: hedge::Mesh = hedge::Mesh::from_indexed_faces(3, &[tri]).unwrap();
+///
+/// let result = subdivide::loop_subdivision(&mesh, 2).unwrap();
+/// assert_eq!(result.faces().count(), 16);
+/// ```
+pub fn loop_subdivision<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, iterations: usize) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+    repeat(mesh, iterations, loop_step)
+}
+
+fn loop_step<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+    for findex in mesh.faces() {
+        if mesh.edges(mesh.face(findex)).count() != 3 {
+            return Err(Error::NonTriangularFace(findex));
+        }
+    }
+
+    let mut builder = MeshBuilder::default();
+
+    // Vertex points, in the order of the original vertices
+    let mut vertex_points = vec![VertexIndex::default(); mesh.vertex_list.len()];
+    for (i, point) in vertex_points.iter_mut().enumerate().skip(1) {
+        let index = mesh.vertex_index_at(i);
+        if !mesh.is_current_vertex(index) {
+            continue;
+        }
+        let vertex = mesh.vertex_fn(index);
+        let position = offset(mesh, index);
+        let boundary = boundary_neighbours(mesh, index);
+        let result = if vertex.is_boundary() {
+            if boundary.len() == 2 {
+                position * 0.75 + (offset(mesh, boundary[0]) + offset(mesh, boundary[1])) * 0.125
+            } else {
+                // Corners of non-manifold or dangling boundaries stay put
+                position
+            }
+        } else {
+            let neighbours: Vec<VertexIndex<Ix>> = vertex.adjacent_vertices().collect();
+            let n = neighbours.len() as f32;
+            if neighbours.is_empty() {
+                position
+            } else {
+                let c = 0.375 + 0.25 * (2.0 * PI / n).cos();
+                let beta = (0.625 - c * c) / n;
+                let sum = neighbours.iter()
+                    .fold(Vector3::new(0.0, 0.0, 0.0), |sum, &v| sum + offset(mesh, v));
+                position * (1.0 - n * beta) + sum * beta
+            }
+        };
+        *point = builder.try_add_vertex_at(Position::from_vec(result))?;
+    }
+
+    // Edge points
+    let (point_of_edge, point_count) = edge_points(mesh);
+    let mut positions = vec![Vector3::new(0.0, 0.0, 0.0); point_count];
+    for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+        let index = mesh.edge_index_at(i);
+        if !mesh.is_current_edge(index) || edge.twin_index.is_valid() && edge.twin_index.offset() < i {
+            continue;
+        }
+        let a = offset(mesh, edge.vertex_index);
+        let b = offset(mesh, mesh.edge_fn(index).next().vertex().index);
+        positions[point_of_edge[i]] = if edge.is_boundary() {
+            (a + b) * 0.5
+        } else {
+            let c = offset(mesh, mesh.edge_fn(index).prev().vertex().index);
+            let d = offset(mesh, mesh.edge_fn(index).twin().prev().vertex().index);
+            (a + b) * 0.375 + (c + d) * 0.125
+        };
+    }
+    let edge_point_of = positions.into_iter()
+        .map(|position| builder.try_add_vertex_at(Position::from_vec(position)))
+        .collect::<Result<Vec<VertexIndex<Ix>>, Error<Ix>>>()?;
+
+    let edge_point = |eindex: EdgeIndex<Ix>| edge_point_of[point_of_edge[eindex.offset()]];
+    for findex in mesh.faces() {
+        let edges: Vec<EdgeIndex<Ix>> = mesh.edges(mesh.face(findex)).collect();
+        let corners: Vec<VertexIndex<Ix>> = edges.iter()
+            .map(|&e| vertex_points[mesh.edge(e).vertex_index.offset()])
+            .collect();
+        let mids: Vec<VertexIndex<Ix>> = edges.iter().map(|&e| edge_point(e)).collect();
+        for i in 0 .. 3 {
+            builder.add_polygon(&[corners[i], mids[i], mids[(i + 2) % 3]])?;
+        }
+        builder.add_polygon(&mids)?;
+    }
+
+    Ok(builder.build())
+}
+
+/// The name of the undirected edge attribute channel holding the crease
+/// sharpness used by `catmull_clark`, with values of type `f32`.
+pub const CREASE_ATTRIBUTE: &str = "crease";
+
+/// Returns the crease sharpness of every half-edge, treating boundary edges
+/// as infinitely sharp.
+fn sharpness<V, E, F, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> Vec<f32> {
+    let creases = mesh.attribute::<f32>(AttributeDomain::UndirectedEdge, CREASE_ATTRIBUTE);
+    mesh.edge_list.iter().enumerate()
+        .map(|(i, edge)| {
+            if edge.is_boundary() {
+                return f32::INFINITY;
+            }
+            match creases {
+                Some(creases) => {
+                    let crease = |eindex| creases.get(eindex).cloned().unwrap_or(0.0);
+                    crease(mesh.edge_index_at(i)).max(crease(edge.twin_index)).max(0.0)
+                },
+                None => 0.0,
+            }
+        })
+        .collect()
+}
+
+fn lerp(a: Vector3<f32>, b: Vector3<f32>, t: f32) -> Vector3<f32> {
+    a + (b - a) * t
+}
+
+/// Subdivides a polygon mesh `iterations` times using the Catmull-Clark
+/// scheme.
+///
+/// Every face with `n` edges is split into `n` quads around a new face
+/// point. Boundary edges are kept sharp, as are edges with a sharpness of
+/// one or more in the `CREASE_ATTRIBUTE` channel. Fractional sharpness
+/// blends between the smooth and sharp rules, and the sharpness of edges is
+/// reduced by one with each level, so that semi-sharp creases soften as
+/// they are refined. The result has a `CREASE_ATTRIBUTE` channel whenever
+/// the input has one.
+///
+/// ```
+/// use hedge::subdivide;
+/// let quad: &[usize] = &[0, 1, 2, 3];
+/// let mesh: hedge::Mesh = hedge::Mesh::from_indexed_faces(4, &[quad]).unwrap();
+///
+/// let result = subdivide::catmull_clark(&mesh, 2).unwrap();
+/// assert_eq!(result.faces().count(), 16);
+/// ```
+pub fn catmull_clark<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>, iterations: usize) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+    repeat(mesh, iterations, catmull_clark_step)
+}
+
+fn catmull_clark_step<V: Default, E: Default, F: Default, Ix: IndexType>(mesh: &Mesh<V, E, F, Ix>) -> Result<Mesh<V, E, F, Ix>, Error<Ix>> {
+    let zero = Vector3::new(0.0, 0.0, 0.0);
+    let sharpness = sharpness(mesh);
+
+    let mut face_points = vec![zero; mesh.face_list.len()];
+    for findex in mesh.faces() {
+        let verts: Vec<VertexIndex<Ix>> = mesh.vertices(mesh.face(findex)).collect();
+        let sum = verts.iter().fold(zero, |sum, &v| sum + offset(mesh, v));
+        face_points[findex.offset()] = sum / verts.len() as f32;
+    }
+
+    let (point_of_edge, point_count) = edge_points(mesh);
+    let mut edge_points = vec![zero; point_count];
+    for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+        let index = mesh.edge_index_at(i);
+        if !mesh.is_current_edge(index) || edge.twin_index.is_valid() && edge.twin_index.offset() < i {
+            continue;
+        }
+        let a = offset(mesh, edge.vertex_index);
+        let b = offset(mesh, mesh.edge_fn(index).next().vertex().index);
+        let sharp = (a + b) * 0.5;
+        let s = sharpness[i];
+        edge_points[point_of_edge[i]] = if s >= 1.0 {
+            sharp
+        } else {
+            let twin_face = mesh.edge(edge.twin_index).face_index;
+            let smooth = (a + b + face_points[edge.face_index.offset()] + face_points[twin_face.offset()]) * 0.25;
+            lerp(smooth, sharp, s)
+        };
+    }
+
+    let mut builder = MeshBuilder::default();
+
+    // Vertex points, in the order of the original vertices
+    let mut vertex_points = vec![VertexIndex::default(); mesh.vertex_list.len()];
+    for (i, point) in vertex_points.iter_mut().enumerate().skip(1) {
+        let index = mesh.vertex_index_at(i);
+        if !mesh.is_current_vertex(index) {
+            continue;
+        }
+        let position = offset(mesh, index);
+        let outgoing: Vec<EdgeIndex<Ix>> = mesh.vertex_fn(index).outgoing_edges().collect();
+
+        // Every edge around the vertex, with the vertex at its other end
+        let mut edges: Vec<(EdgeIndex<Ix>, VertexIndex<Ix>)> = outgoing.iter()
+            .map(|&e| (e, mesh.edge_fn(e).next().vertex().index))
+            .collect();
+        if let Some(&first) = outgoing.first() {
+            let incoming = mesh.edge(first).prev_index;
+            if mesh.edge(incoming).is_boundary() {
+                edges.push((incoming, mesh.edge(incoming).vertex_index));
+            }
+        }
+
+        let creases: Vec<(EdgeIndex<Ix>, VertexIndex<Ix>)> = edges.iter()
+            .cloned()
+            .filter(|&(e, _)| sharpness[e.offset()] > 0.0)
+            .collect();
+        let result = if edges.is_empty() || creases.len() > 2 {
+            position
+        } else if creases.len() < 2 {
+            smooth_vertex_point(mesh, &outgoing, &edges, &face_points, position)
+        } else {
+            let ((e1, v1), (e2, v2)) = (creases[0], creases[1]);
+            let crease = (offset(mesh, v1) + position * 6.0 + offset(mesh, v2)) / 8.0;
+            let s = (sharpness[e1.offset()] + sharpness[e2.offset()]) * 0.5;
+            if s >= 1.0 {
+                crease
+            } else {
+                lerp(smooth_vertex_point(mesh, &outgoing, &edges, &face_points, position), crease, s)
+            }
+        };
+        *point = builder.try_add_vertex_at(Position::from_vec(result))?;
+    }
+
+    let edge_point_of = edge_points.iter()
+        .map(|&point| builder.try_add_vertex_at(Position::from_vec(point)))
+        .collect::<Result<Vec<VertexIndex<Ix>>, Error<Ix>>>()?;
+    let mut face_point_of = vec![VertexIndex::default(); mesh.face_list.len()];
+    for findex in mesh.faces() {
+        face_point_of[findex.offset()] = builder.try_add_vertex_at(Position::from_vec(face_points[findex.offset()]))?;
+    }
+
+    let edge_point = |eindex: EdgeIndex<Ix>| edge_point_of[point_of_edge[eindex.offset()]];
+    for findex in mesh.faces() {
+        let edges: Vec<EdgeIndex<Ix>> = mesh.edges(mesh.face(findex)).collect();
+        let n = edges.len();
+        for i in 0 .. n {
+            builder.add_polygon(&[
+                vertex_points[mesh.edge(edges[i]).vertex_index.offset()],
+                edge_point(edges[i]),
+                face_point_of[findex.offset()],
+                edge_point(edges[(i + n - 1) % n]),
+            ])?;
+        }
+    }
+    let mut result = builder.build();
+
+    // Creases lose one level of sharpness on each of their halves
+    if mesh.has_attribute(AttributeDomain::UndirectedEdge, CREASE_ATTRIBUTE) {
+        result.add_attribute::<f32>(AttributeDomain::UndirectedEdge, CREASE_ATTRIBUTE);
+        for (i, edge) in mesh.edge_list.iter().enumerate().skip(1) {
+            let s = sharpness[i];
+            if edge.is_boundary() || s <= 1.0 {
+                continue;
+            }
+            let index = mesh.edge_index_at(i);
+            let middle = edge_point(index);
+            let end = vertex_points[mesh.edge_fn(index).next().vertex().index.offset()];
+            for &(from, to) in &[(vertex_points[edge.vertex_index.offset()], middle), (middle, end)] {
+                if let Some(eindex) = result.find_edge(from, to) {
+                    result.set_undirected_edge_attribute(CREASE_ATTRIBUTE, eindex, s - 1.0)?;
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// The Catmull-Clark vertex point of an interior vertex, `(Q + 2R + (n - 3)S) / n`.
+fn smooth_vertex_point<V, E, F, Ix: IndexType>(
+    mesh: &Mesh<V, E, F, Ix>,
+    outgoing: &[EdgeIndex<Ix>],
+    edges: &[(EdgeIndex<Ix>, VertexIndex<Ix>)],
+    face_points: &[Vector3<f32>],
+    position: Vector3<f32>
+) -> Vector3<f32> {
+    let zero = Vector3::new(0.0, 0.0, 0.0);
+    let n = edges.len() as f32;
+    let faces: Vec<FaceIndex<Ix>> = outgoing.iter()
+        .map(|&e| mesh.edge(e).face_index)
+        .filter(|f| f.is_valid())
+        .collect();
+    if faces.is_empty() {
+        return position;
+    }
+    let q = faces.iter().fold(zero, |sum, f| sum + face_points[f.offset()]) / faces.len() as f32;
+    let r = edges.iter().fold(zero, |sum, &(_, v)| sum + (position + offset(mesh, v)) * 0.5) / n;
+    (q + r * 2.0 + position * (n - 3.0)) / n
+}
@@ -1,14 +1,16 @@
+use std::mem;
+
 use super::*;
 
 type TestMesh = Mesh;
 
 #[test]
 fn basic_debug_printing() {
-    let edge = Edge::default();
+    let edge: Edge = Edge::default();
     println!("{:?}", edge);
-    let vertex = Vertex::default();
+    let vertex: Vertex = Vertex::default();
     println!("{:?}", vertex);
-    let face = Face::default();
+    let face: Face = Face::default();
     println!("{:?}", face);
     let mesh = TestMesh::new();
     println!("{:?}", mesh);
@@ -17,28 +19,28 @@ fn basic_debug_printing() {
 
 #[test]
 fn index_types_are_invalid_by_default() {
-    let vert = EdgeIndex::default();
-    let edge = EdgeIndex::default();
+    let vert: VertexIndex = VertexIndex::default();
+    let edge: EdgeIndex = EdgeIndex::default();
     assert!(!vert.is_valid());
     assert!(!edge.is_valid());
 }
 
 #[test]
 fn default_edge_is_invalid() {
-    let edge = Edge::default();
-    assert!(edge.is_valid() == false);
+    let edge: Edge = Edge::default();
+    assert!(!edge.is_valid());
 }
 
 #[test]
 fn default_vertex_is_invalid() {
-    let vertex = Vertex::default();
-    assert!(vertex.is_valid() == false);
+    let vertex: Vertex = Vertex::default();
+    assert!(!vertex.is_valid());
 }
 
 #[test]
 fn default_face_is_invalid() {
-    let face = Face::default();
-    assert!(face.is_valid() == false);
+    let face: Face = Face::default();
+    assert!(!face.is_valid());
 }
 
 #[test]
@@ -46,24 +48,24 @@ fn initial_mesh_has_default_elements() {
     let mesh = Mesh::new();
     assert! {
         mesh.edge_list.len() == 1 &&
-            mesh.edge_list[0].is_valid() == false
+            !mesh.edge_list[0].is_valid()
     };
     assert! {
         mesh.vertex_list.len() == 1 &&
-            mesh.vertex_list[0].is_valid() == false
+            !mesh.vertex_list[0].is_valid()
     };
     assert! {
         mesh.face_list.len() == 1 &&
-            mesh.face_list[0].is_valid() == false
+            !mesh.face_list[0].is_valid()
     };
 }
 
 #[test]
 fn can_iterate_over_faces() {
     let mut mesh = TestMesh::new();
-    mesh.face_list.push(Face::new(EdgeIndex(1)));
-    mesh.face_list.push(Face::new(EdgeIndex(4)));
-    mesh.face_list.push(Face::new(EdgeIndex(7)));
+    mesh.face_list.push(Face::new(EdgeIndex(1, 0)));
+    mesh.face_list.push(Face::new(EdgeIndex(4, 0)));
+    mesh.face_list.push(Face::new(EdgeIndex(7, 0)));
 
     assert!(mesh.face_list.len() == 4);
 
@@ -81,31 +83,34 @@ fn can_iterate_over_faces() {
 #[test]
 fn can_iterate_over_edges_of_face() {
     let mut mesh = TestMesh::new();
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(1)));
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(2)));
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(3)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(1, 0)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(2, 0)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(3, 0)));
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(2),
-        prev_index: EdgeIndex(3),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(1)
+        next_index: EdgeIndex(2, 0),
+        prev_index: EdgeIndex(3, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(1, 0),
+        data: ()
     });
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(3),
-        prev_index: EdgeIndex(1),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(2)
+        next_index: EdgeIndex(3, 0),
+        prev_index: EdgeIndex(1, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(2, 0),
+        data: ()
     });
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(1),
-        prev_index: EdgeIndex(2),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(3)
+        next_index: EdgeIndex(1, 0),
+        prev_index: EdgeIndex(2, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(3, 0),
+        data: ()
     });
-    mesh.face_list.push(Face::new(EdgeIndex(1)));
+    mesh.face_list.push(Face::new(EdgeIndex(1, 0)));
 
     assert!(mesh.vertex_list.len() == 4);
     assert!(mesh.edge_list.len() == 4);
@@ -133,31 +138,34 @@ fn can_iterate_over_edges_of_face() {
 #[test]
 fn can_iterate_over_vertices_of_face() {
     let mut mesh = TestMesh::new();
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(1)));
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(2)));
-    mesh.vertex_list.push(Vertex::new(EdgeIndex(3)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(1, 0)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(2, 0)));
+    mesh.vertex_list.push(Vertex::new(EdgeIndex(3, 0)));
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(2),
-        prev_index: EdgeIndex(3),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(1)
+        next_index: EdgeIndex(2, 0),
+        prev_index: EdgeIndex(3, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(1, 0),
+        data: ()
     });
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(3),
-        prev_index: EdgeIndex(1),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(2)
+        next_index: EdgeIndex(3, 0),
+        prev_index: EdgeIndex(1, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(2, 0),
+        data: ()
     });
     mesh.edge_list.push(Edge {
         twin_index: EdgeIndex::default(),
-        next_index: EdgeIndex(1),
-        prev_index: EdgeIndex(2),
-        face_index: FaceIndex(1),
-        vertex_index: VertexIndex(3)
+        next_index: EdgeIndex(1, 0),
+        prev_index: EdgeIndex(2, 0),
+        face_index: FaceIndex(1, 0),
+        vertex_index: VertexIndex(3, 0),
+        data: ()
     });
-    mesh.face_list.push(Face::new(EdgeIndex(1)));
+    mesh.face_list.push(Face::new(EdgeIndex(1, 0)));
 
     assert!(mesh.vertex_list.len() == 4);
     assert!(mesh.edge_list.len() == 4);
@@ -195,7 +203,7 @@ fn can_add_triangles_to_mesh() {
 
     let f1 = mesh.add_triangle(v1, v2, v4);
     for eindex in mesh.edges(mesh.face(f1)) {
-        let ref edge = mesh.edge(eindex);
+        let edge = mesh.edge(eindex);
         assert!(edge.next_index.is_valid());
         assert!(edge.prev_index.is_valid());
     }
@@ -205,7 +213,7 @@ fn can_add_triangles_to_mesh() {
 
     let f2 = mesh.add_adjacent_triangle(v3, twin_a);
     for eindex in mesh.edges(mesh.face(f1)) {
-        let ref edge = mesh.edge(eindex);
+        let edge = mesh.edge(eindex);
         assert!(edge.next_index.is_valid());
         assert!(edge.prev_index.is_valid());
     }
@@ -239,7 +247,7 @@ fn can_walk_and_get_mutable_ref() {
         vertex.edge_index = EdgeIndex::default();
     }
 
-    assert!(mesh.face_fn(f1).edge().vertex().edge().index.is_valid() == false);
+    assert!(!mesh.face_fn(f1).edge().vertex().edge().index.is_valid());
 }
 
 #[test]
@@ -313,3 +321,1894 @@ fn can_build_a_simple_mesh() {
         (f2_prev_vert == v4) && (f3_prev_vert == v4) && (f4_prev_vert == v4)
     };
 }
+
+/// Builds a closed tetrahedron where every edge has a twin.
+fn build_tetrahedron() -> (TestMesh, [VertexIndex; 4], [FaceIndex; 4]) {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    let e1 = mesh.face_fn(f1).edge().index;
+    let f2 = mesh.add_adjacent_triangle(v4, e1);
+    let e2 = mesh.face_fn(f1).edge().next().index;
+    let f3 = mesh.add_adjacent_triangle(v4, e2);
+    let e3 = mesh.face_fn(f1).edge().prev().index;
+    let f4 = mesh.add_adjacent_triangle(v4, e3);
+
+    let (a, b) = (mesh.face_fn(f2).edge().prev().index, mesh.face_fn(f3).edge().next().index);
+    mesh.set_twin_edges(a, b);
+    let (a, b) = (mesh.face_fn(f3).edge().prev().index, mesh.face_fn(f4).edge().next().index);
+    mesh.set_twin_edges(a, b);
+    let (a, b) = (mesh.face_fn(f4).edge().prev().index, mesh.face_fn(f2).edge().next().index);
+    mesh.set_twin_edges(a, b);
+
+    (mesh, [v1, v2, v3, v4], [f1, f2, f3, f4])
+}
+
+#[test]
+fn remove_vertex_refuses_while_edges_remain() {
+    let (mut mesh, verts, _) = build_tetrahedron();
+
+    let result = mesh.remove_vertex(verts[3], RemovalPolicy::Refuse);
+    assert_eq!(result, Err(Error::VertexInUse(verts[3])));
+    assert_eq!(mesh.vertex_list.len(), 5);
+    assert_eq!(mesh.edge_list.len(), 13);
+    assert_eq!(mesh.face_list.len(), 5);
+}
+
+#[test]
+fn can_remove_isolated_vertex() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+    let f1 = mesh.add_triangle(v2, v3, v4);
+
+    assert!(mesh.remove_vertex(v1, RemovalPolicy::Refuse).is_ok());
+    assert_eq!(mesh.vertex_list.len(), 4);
+
+    // v4 was moved into the slot previously occupied by v1
+    let moved = mesh.vertex_index_at(v1.0);
+    let verts: Vec<VertexIndex> = mesh.vertices(mesh.face(f1)).collect();
+    assert_eq!(verts, vec![v2, v3, moved]);
+    assert!(mesh.vertex_fn(moved).edge().vertex().index == moved);
+}
+
+#[test]
+fn can_remove_vertex_and_incident_components() {
+    let (mut mesh, verts, faces) = build_tetrahedron();
+
+    assert!(mesh.remove_vertex(verts[3], RemovalPolicy::Cascade).is_ok());
+    assert_eq!(mesh.vertex_list.len(), 4);
+    assert_eq!(mesh.edge_list.len(), 7);
+    assert_eq!(mesh.face_list.len(), 2);
+
+    let remaining: Vec<VertexIndex> = mesh.vertices(mesh.face(faces[0])).collect();
+    assert_eq!(remaining, vec![verts[0], verts[1], verts[2]]);
+    for eindex in mesh.edges(mesh.face(faces[0])) {
+        assert!(mesh.edge(eindex).is_valid());
+    }
+    for vindex in remaining {
+        assert!(mesh.vertex_fn(vindex).edge().vertex().index == vindex);
+    }
+}
+
+#[test]
+fn removing_vertex_updates_swapped_vertex() {
+    let (mut mesh, verts, _) = build_tetrahedron();
+
+    assert!(mesh.remove_vertex(verts[0], RemovalPolicy::Cascade).is_ok());
+    assert_eq!(mesh.vertex_list.len(), 4);
+    assert_eq!(mesh.face_list.len(), 2);
+
+    // The only remaining face is the one opposite the removed vertex, and the
+    // last vertex now lives at the index of the removed one.
+    let face = mesh.face(mesh.face_index_at(1));
+    let remaining: Vec<VertexIndex> = mesh.vertices(face).collect();
+    assert_eq!(remaining.len(), 3);
+    assert!(remaining.contains(&mesh.vertex_index_at(verts[0].0)));
+    assert!(remaining.contains(&verts[1]));
+    assert!(remaining.contains(&verts[2]));
+    for edge in &mesh.edge_list[1..] {
+        assert!(edge.vertex_index.0 < mesh.vertex_list.len());
+    }
+}
+
+#[test]
+fn can_add_quads_to_mesh() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+
+    let f1 = mesh.add_polygon(&[v1, v2, v3, v4]);
+    let verts: Vec<VertexIndex> = mesh.vertices(mesh.face(f1)).collect();
+    assert_eq!(verts, vec![v1, v2, v3, v4]);
+    for eindex in mesh.edges(mesh.face(f1)) {
+        assert!(mesh.edge(eindex).is_valid());
+    }
+}
+
+#[test]
+fn try_add_polygon_rejects_bad_input() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+
+    assert_eq!(mesh.try_add_polygon(&[v1, v2]), Err(Error::DegeneratePolygon));
+    assert_eq!(mesh.try_add_triangle(v1, v2, v1), Err(Error::DegeneratePolygon));
+    assert_eq!(mesh.try_add_triangle(v1, v2, VertexIndex::default()),
+               Err(Error::InvalidVertexIndex(VertexIndex::default())));
+    assert_eq!(mesh.try_add_polygon(&[v1, v2, v3, VertexIndex(42, 0)]),
+               Err(Error::InvalidVertexIndex(VertexIndex(42, 0))));
+    assert_eq!(mesh.edge_list.len(), 1);
+    assert_eq!(mesh.face_list.len(), 1);
+
+    assert!(mesh.try_add_triangle(v1, v2, v3).is_ok());
+}
+
+#[test]
+fn try_set_twin_edges_rejects_mismatched_edges() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    let f2 = mesh.add_triangle(v2, v1, v4);
+    let e1 = mesh.face_fn(f1).edge().index;
+    let e2 = mesh.face_fn(f2).edge().index;
+    let e3 = mesh.face_fn(f2).edge().next().index;
+
+    assert_eq!(mesh.try_set_twin_edges(e1, e3), Err(Error::MismatchedTwins(e1, e3)));
+    assert!(!mesh.edge(e1).twin_index.is_valid());
+
+    assert!(mesh.try_set_twin_edges(e1, e2).is_ok());
+    assert_eq!(mesh.edge(e1).twin_index, e2);
+    assert_eq!(mesh.try_set_twin_edges(e3, e1), Err(Error::DanglingTwin(e1)));
+}
+
+#[test]
+fn try_add_adjacent_triangle_rejects_non_manifold_edges() {
+    let (mut mesh, _, faces) = build_tetrahedron();
+
+    let eindex = mesh.face_fn(faces[0]).edge().index;
+    let v5 = mesh.add_vertex(Vertex::default());
+    assert_eq!(mesh.try_add_adjacent_triangle(v5, eindex), Err(Error::NonManifoldEdge(eindex)));
+    assert_eq!(mesh.face_list.len(), 5);
+
+    let mut mesh = TestMesh::new();
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    let eindex = mesh.face_fn(f1).edge().index;
+    assert_eq!(mesh.try_add_adjacent_triangle(v2, eindex), Err(Error::DegeneratePolygon));
+    let v4 = mesh.add_vertex(Vertex::default());
+    assert!(mesh.try_add_adjacent_triangle(v4, eindex).is_ok());
+}
+
+#[test]
+fn try_methods_reject_broken_loops() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+
+    let e1 = mesh.edge_from_vertex(v1);
+    let e2 = mesh.try_extend_edge_loop(v2, e1).unwrap();
+    assert_eq!(mesh.try_extend_edge_loop(v2, e1), Err(Error::EdgeAlreadyConnected(e1)));
+
+    let face = mesh.add_face(Face::default());
+    assert_eq!(mesh.try_assign_face_to_loop(face, e1), Err(Error::OpenEdgeLoop(e1)));
+    assert!(!mesh.edge(e1).face_index.is_valid());
+
+    assert_eq!(mesh.try_remove_edge(EdgeIndex(9, 0)), Err(Error::InvalidEdgeIndex(EdgeIndex(9, 0))));
+    assert_eq!(mesh.try_remove_face(FaceIndex::default()),
+               Err(Error::InvalidFaceIndex(FaceIndex::default())));
+    assert_eq!(mesh.try_add_vertex(Vertex::new(EdgeIndex(9, 0))),
+               Err(Error::InvalidEdgeIndex(EdgeIndex(9, 0))));
+
+    mesh.connect_edges(e2, e1);
+    assert!(mesh.try_assign_face_to_loop(face, e1).is_ok());
+    assert_eq!(mesh.edge(e2).face_index, face);
+}
+
+#[test]
+fn tetrahedron_passes_integrity_check() {
+    let (mesh, _, _) = build_tetrahedron();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn integrity_check_reports_broken_connectivity() {
+    let (mut mesh, verts, faces) = build_tetrahedron();
+
+    let e1 = mesh.face_fn(faces[0]).edge().index;
+    let e2 = mesh.face_fn(faces[0]).edge().next().index;
+    let twin = mesh.edge(e1).twin_index;
+    mesh.edge_mut(e1).unwrap().twin_index = e2;
+    mesh.edge_mut(e2).unwrap().prev_index = EdgeIndex::default();
+    mesh.vertex_mut(verts[3]).unwrap().edge_index = e1;
+    mesh.face_mut(faces[1]).unwrap().edge_index = EdgeIndex(99, 0);
+
+    let violations = mesh.check_integrity().violations;
+    assert!(violations.contains(&Violation::VertexEdgeMismatch(verts[3])));
+    assert!(violations.contains(&Violation::TwinMismatch(e1)));
+    assert!(violations.contains(&Violation::TwinMismatch(twin)));
+    assert!(violations.contains(&Violation::DisconnectedEdge(e2)));
+    assert!(violations.contains(&Violation::NextPrevMismatch(e1)));
+    assert!(violations.contains(&Violation::OutOfBounds(Component::Face(faces[1]))));
+}
+
+#[test]
+fn integrity_check_reports_mismatched_twins_and_faces() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    let f2 = mesh.add_triangle(v2, v1, v4);
+    let e1 = mesh.face_fn(f1).edge().index;
+    let e2 = mesh.face_fn(f2).edge().next().index;
+    mesh.set_twin_edges(e1, e2);
+
+    let e3 = mesh.face_fn(f2).edge().prev().index;
+    mesh.edge_mut(e3).unwrap().face_index = f1;
+
+    let violations = mesh.check_integrity().violations;
+    assert!(violations.contains(&Violation::TwinVertexMismatch(e1)));
+    assert!(violations.contains(&Violation::TwinVertexMismatch(e2)));
+    assert!(violations.contains(&Violation::FaceMismatch(e2)));
+    assert!(violations.contains(&Violation::FaceMismatch(e3)));
+}
+
+#[test]
+fn can_circulate_around_interior_vertex() {
+    let (mesh, verts, _) = build_tetrahedron();
+
+    for &vindex in &verts {
+        let vertex = mesh.vertex_fn(vindex);
+        assert!(!vertex.is_boundary());
+
+        let outgoing: Vec<EdgeIndex> = vertex.outgoing_edges().collect();
+        assert_eq!(outgoing.len(), 3);
+        for &eindex in &outgoing {
+            assert_eq!(mesh.edge(eindex).vertex_index, vindex);
+        }
+
+        let incoming: Vec<EdgeIndex> = vertex.incoming_edges().collect();
+        assert_eq!(incoming.len(), 3);
+        for &eindex in &incoming {
+            assert_eq!(mesh.edge_fn(eindex).next().vertex().index, vindex);
+        }
+
+        let mut adjacent: Vec<VertexIndex> = vertex.adjacent_vertices().collect();
+        adjacent.sort_by_key(|v| v.0);
+        let expected: Vec<VertexIndex> = verts.iter().cloned().filter(|&v| v != vindex).collect();
+        assert_eq!(adjacent, expected);
+
+        assert_eq!(vertex.incident_faces().count(), 3);
+    }
+}
+
+#[test]
+fn can_circulate_around_boundary_vertex() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    let shared = mesh.face_fn(f1).edge().prev().index;
+    let f2 = mesh.add_adjacent_triangle(v4, shared);
+
+    let expected: Vec<EdgeIndex> = mesh.vertex_fn(v1).outgoing_edges().collect();
+    assert_eq!(expected.len(), 2);
+    // The order doesn't depend on which outgoing edge the vertex refers to
+    for &eindex in &expected {
+        mesh.vertex_mut(v1).unwrap().edge_index = eindex;
+        let outgoing: Vec<EdgeIndex> = mesh.vertex_fn(v1).outgoing_edges().collect();
+        assert_eq!(outgoing, expected);
+    }
+
+    let vertex = mesh.vertex_fn(v1);
+    assert!(vertex.is_boundary());
+    assert_eq!(vertex.incoming_edges().count(), 2);
+
+    let mut adjacent: Vec<VertexIndex> = vertex.adjacent_vertices().collect();
+    adjacent.sort_by_key(|v| v.0);
+    assert_eq!(adjacent, vec![v2, v3, v4]);
+
+    let mut faces: Vec<FaceIndex> = vertex.incident_faces().collect();
+    faces.sort_by_key(|f| f.0);
+    assert_eq!(faces, vec![f1, f2]);
+
+    let adjacent: Vec<VertexIndex> = mesh.vertex_fn(v2).adjacent_vertices().collect();
+    assert_eq!(adjacent, vec![v3, v1]);
+}
+
+#[test]
+fn isolated_vertex_has_no_neighbours() {
+    let mut mesh = TestMesh::new();
+    let v1 = mesh.add_vertex(Vertex::default());
+
+    let vertex = mesh.vertex_fn(v1);
+    assert_eq!(vertex.outgoing_edges().count(), 0);
+    assert_eq!(vertex.incoming_edges().count(), 0);
+    assert_eq!(vertex.adjacent_vertices().count(), 0);
+    assert_eq!(vertex.incident_faces().count(), 0);
+    assert!(!vertex.is_boundary());
+}
+
+const CUBE_FACES: [[usize; 4]; 6] = [
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+    [0, 1, 5, 4],
+    [1, 2, 6, 5],
+    [2, 3, 7, 6],
+    [3, 0, 4, 7],
+];
+
+const CUBE_POSITIONS: [[f32; 3]; 8] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 1.0, 1.0],
+    [0.0, 1.0, 1.0],
+];
+
+/// Builds a mesh from indexed polygons, returning it along with the indices
+/// of its vertices and faces in the order they were given.
+fn build_mesh<T: AsRef<[usize]>>(positions: &[[f32; 3]], faces: &[T]) -> (TestMesh, Vec<VertexIndex>, Vec<FaceIndex>) {
+    let polygons: Vec<&[usize]> = faces.iter().map(|f| f.as_ref()).collect();
+    let mut mesh = TestMesh::from_indexed_faces(positions.len(), &polygons).unwrap();
+    let verts: Vec<VertexIndex> = (1 ..= positions.len()).map(|i| VertexIndex(i, 0)).collect();
+    for (&vindex, p) in verts.iter().zip(positions) {
+        mesh.set_position(vindex, Position::new(p[0], p[1], p[2]));
+    }
+    let faces = (1 ..= faces.len()).map(|i| FaceIndex(i, 0)).collect();
+    (mesh, verts, faces)
+}
+
+#[test]
+fn can_build_closed_mesh_from_indexed_faces() {
+    let (mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+
+    assert_eq!(mesh.vertex_list.len(), 9);
+    assert_eq!(mesh.edge_list.len(), 25);
+    assert_eq!(mesh.face_list.len(), 7);
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+
+    for edge in &mesh.edge_list[1..] {
+        assert!(!edge.is_boundary());
+    }
+    for index in 1 .. mesh.vertex_list.len() {
+        assert_eq!(mesh.vertex_fn(VertexIndex(index, 0)).outgoing_edges().count(), 3);
+    }
+}
+
+#[test]
+fn from_indexed_faces_reports_non_manifold_edges() {
+    let tri_a: &[usize] = &[0, 1, 2];
+    let tri_b: &[usize] = &[1, 0, 3];
+    let tri_c: &[usize] = &[0, 1, 4];
+    let result = TestMesh::from_indexed_faces(5, &[tri_a, tri_b, tri_c]);
+    assert_eq!(result.unwrap_err(), Error::NonManifoldEdge(EdgeIndex(1, 0)));
+
+    let tri_c: &[usize] = &[4, 1, 0];
+    let result = TestMesh::from_indexed_faces(5, &[tri_a, tri_b, tri_c]);
+    assert_eq!(result.unwrap_err(), Error::NonManifoldEdge(EdgeIndex(4, 0)));
+
+    let result = TestMesh::from_indexed_faces(3, &[tri_a, tri_b]);
+    assert_eq!(result.unwrap_err(), Error::InvalidVertexIndex(VertexIndex(4, 0)));
+}
+
+#[test]
+fn builder_can_continue_an_existing_mesh() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+    let f1 = mesh.add_triangle(v1, v2, v3);
+
+    let mut builder = MeshBuilder::from_mesh(mesh);
+    let f2 = builder.add_polygon(&[v3, v2, v4]).unwrap();
+    let f3 = builder.add_polygon(&[v4, v2, v1]).unwrap();
+    assert_eq!(builder.add_polygon(&[v2, v4, v3]).unwrap_err(),
+               Error::NonManifoldEdge(builder.mesh().face_fn(f2).edge().next().index));
+
+    let mesh = builder.build();
+    assert!(mesh.check_integrity().is_valid());
+    assert_eq!(mesh.face_fn(f1).edge().next().twin().face().index, f2);
+    assert_eq!(mesh.face_fn(f2).edge().next().twin().face().index, f3);
+    assert_eq!(mesh.face_fn(f3).edge().next().twin().face().index, f1);
+    let boundary = mesh.face_fn(f1).edge().prev().index;
+    assert!(mesh.edge(boundary).is_boundary());
+}
+
+#[cfg(feature = "obj")]
+const OBJ_SOURCE: &str = "
+# two quads and a triangle sharing edges
+mtllib cube.mtl
+o Thing
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+v 2 1 0
+v 0.5 2 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+g left
+usemtl red
+f 1/1/1 2/2/1 3/3/1 4/4/1
+g right
+usemtl blue
+f -6//-1 -3//-1 -2//-1 \\
+  -5//-1
+f 4 3 7
+";
+
+#[cfg(feature = "obj")]
+#[test]
+fn can_read_obj() {
+    let obj = obj::read(OBJ_SOURCE.as_bytes()).unwrap();
+    let mesh = &obj.mesh;
+
+    assert_eq!(mesh.vertex_list.len(), 8);
+    assert_eq!(mesh.face_list.len(), 4);
+    assert_eq!(mesh.edge_list.len(), 12);
+    assert!(mesh.check_integrity().is_valid());
+    assert_eq!(mesh.position_list.len(), 8);
+    assert_eq!(*mesh.vertex_fn(VertexIndex(7, 0)).position(), Position::new(0.5, 2.0, 0.0));
+
+    let f2 = FaceIndex(2, 0);
+    let verts: Vec<VertexIndex> = mesh.vertices(mesh.face(f2)).collect();
+    assert_eq!(verts, vec![VertexIndex(2, 0), VertexIndex(5, 0), VertexIndex(6, 0), VertexIndex(3, 0)]);
+
+    // The quads share the edge between vertices 2 and 3, the triangle shares
+    // the edge between 3 and 4 with the first quad.
+    let boundary_count = mesh.edge_list[1..].iter().filter(|e| e.is_boundary()).count();
+    assert_eq!(boundary_count, 7);
+    assert_eq!(mesh.face_fn(FaceIndex(1, 0)).edge().next().twin().face().index, f2);
+    assert_eq!(mesh.face_fn(FaceIndex(1, 0)).edge().next().next().twin().face().index, FaceIndex(3, 0));
+
+    let e1 = mesh.face_fn(FaceIndex(1, 0)).edge().next().index;
+    assert_eq!(corner_tex_coord(mesh, e1), Some(cgmath::Vector2::new(1.0, 0.0)));
+    assert_eq!(corner_normal(mesh, e1), Some(cgmath::Vector3::new(0.0, 0.0, 1.0)));
+    let e2 = mesh.face_fn(f2).edge().index;
+    assert_eq!(corner_tex_coord(mesh, e2), None);
+    assert_eq!(corner_normal(mesh, e2), Some(cgmath::Vector3::new(0.0, 0.0, 1.0)));
+    let e3 = mesh.face_fn(FaceIndex(3, 0)).edge().index;
+    assert_eq!(corner_normal(mesh, e3), None);
+
+    assert_eq!(obj.objects, vec!["Thing".to_string()]);
+    assert_eq!(obj.groups, vec!["left".to_string(), "right".to_string()]);
+    assert_eq!(obj.materials, vec!["red".to_string(), "blue".to_string()]);
+    assert_eq!(face_tags(mesh, FaceIndex(1, 0)), obj::FaceTags { object: Some(0), group: Some(0), material: Some(0) });
+    assert_eq!(face_tags(mesh, FaceIndex(3, 0)), obj::FaceTags { object: Some(0), group: Some(1), material: Some(1) });
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn reading_obj_reports_errors() {
+    match obj::read("v 0 0 0\nv 1 0 0\nf 1 2 3\n".as_bytes()) {
+        Err(obj::ObjError::Parse { line: 3, .. }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    match obj::read("v 0 0\n".as_bytes()) {
+        Err(obj::ObjError::Parse { line: 1, .. }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 1 2 4\n";
+    match obj::read(source.as_bytes()) {
+        Err(obj::ObjError::Mesh { line: 6, error: Error::NonManifoldEdge(_) }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[cfg(feature = "obj")]
+fn corner_tex_coord(mesh: &Mesh, eindex: EdgeIndex) -> Option<cgmath::Vector2<f32>> {
+    mesh.attribute::<Option<cgmath::Vector2<f32>>>(AttributeDomain::Edge, obj::TEX_COORD_ATTRIBUTE).unwrap()[eindex]
+}
+
+#[cfg(feature = "obj")]
+fn corner_normal(mesh: &Mesh, eindex: EdgeIndex) -> Option<cgmath::Vector3<f32>> {
+    mesh.attribute::<Option<cgmath::Vector3<f32>>>(AttributeDomain::Edge, obj::CORNER_NORMAL_ATTRIBUTE).unwrap()[eindex]
+}
+
+#[cfg(feature = "obj")]
+fn face_tags(mesh: &Mesh, findex: FaceIndex) -> obj::FaceTags {
+    mesh.attribute::<obj::FaceTags>(AttributeDomain::Face, obj::FACE_TAGS_ATTRIBUTE).unwrap()[findex]
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn can_write_obj_and_read_it_back() {
+    let original = obj::read(OBJ_SOURCE.as_bytes()).unwrap();
+
+    let mut output = Vec::new();
+    obj::write(&mut output, &original).unwrap();
+    let copy = obj::read(&output[..]).unwrap();
+
+    assert_eq!(copy.mesh.position_list, original.mesh.position_list);
+    assert_eq!(copy.objects, original.objects);
+    assert_eq!(copy.groups, original.groups);
+    assert_eq!(copy.materials, original.materials);
+    assert_eq!(copy.mesh.face_list.len(), original.mesh.face_list.len());
+    for findex in original.mesh.faces() {
+        let a = original.mesh.face(findex);
+        let b = copy.mesh.face(findex);
+        let verts_a: Vec<VertexIndex> = original.mesh.vertices(a).collect();
+        let verts_b: Vec<VertexIndex> = copy.mesh.vertices(b).collect();
+        assert_eq!(verts_a, verts_b);
+        assert_eq!(face_tags(&copy.mesh, findex), face_tags(&original.mesh, findex));
+        for (ea, eb) in original.mesh.edges(a).zip(copy.mesh.edges(b)) {
+            assert_eq!(corner_tex_coord(&original.mesh, ea), corner_tex_coord(&copy.mesh, eb));
+            assert_eq!(corner_normal(&original.mesh, ea), corner_normal(&copy.mesh, eb));
+        }
+    }
+
+    let text = String::from_utf8(output).unwrap();
+    assert_eq!(text.lines().filter(|l| l.starts_with("vn ")).count(), 1);
+    assert_eq!(text.lines().filter(|l| l.starts_with("usemtl ")).count(), 2);
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn writing_obj_skips_tombstones() {
+    let mut original = obj::read(OBJ_SOURCE.as_bytes()).unwrap();
+    original.mesh.set_removal_mode(RemovalMode::Tombstone);
+    assert!(original.mesh.remove_vertex(VertexIndex(1, 0), RemovalPolicy::Cascade).is_ok());
+
+    let mut output = Vec::new();
+    obj::write(&mut output, &original).unwrap();
+    let text = String::from_utf8(output).unwrap();
+    assert_eq!(text.lines().filter(|l| l.starts_with("v ")).count(), 6);
+    assert_eq!(text.lines().filter(|l| l.starts_with("f ")).count(), 2);
+
+    let copy = obj::read(text.as_bytes()).unwrap();
+    assert!(copy.mesh.check_integrity().is_valid());
+    assert_eq!(copy.mesh.vertex_count(), 6);
+    let faces: Vec<FaceIndex> = original.mesh.faces().collect();
+    assert_eq!(faces.len(), copy.mesh.face_count());
+    for (a, b) in faces.into_iter().zip(copy.mesh.faces()) {
+        let (tags_a, tags_b) = (face_tags(&original.mesh, a), face_tags(&copy.mesh, b));
+        assert_eq!(original.groups[tags_a.group.unwrap()], copy.groups[tags_b.group.unwrap()]);
+        assert_eq!(original.materials[tags_a.material.unwrap()], copy.materials[tags_b.material.unwrap()]);
+        let corners_a = original.mesh.vertices(original.mesh.face(a)).map(|v| *original.mesh.position(v));
+        let corners_b: Vec<Position> = copy.mesh.vertices(copy.mesh.face(b)).map(|v| *copy.mesh.position(v)).collect();
+        assert_eq!(corners_a.collect::<Vec<_>>(), corners_b);
+        for (ea, eb) in original.mesh.edges(original.mesh.face(a)).zip(copy.mesh.edges(copy.mesh.face(b))) {
+            assert_eq!(corner_normal(&original.mesh, ea), corner_normal(&copy.mesh, eb));
+        }
+    }
+
+    // Compacting moves the corner channels along with the edges
+    let corner_normals = |mesh: &Mesh| -> Vec<_> {
+        mesh.faces()
+            .flat_map(|f| mesh.edges(mesh.face(f)).collect::<Vec<_>>())
+            .map(|e| corner_normal(mesh, e))
+            .collect()
+    };
+    let expected = corner_normals(&original.mesh);
+    original.mesh.compact();
+    let compacted = corner_normals(&original.mesh);
+    assert_eq!(compacted, expected);
+    assert!(compacted.iter().any(|n| n.is_some()));
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn obj_tags_survive_editing() {
+    let mut edited = obj::read(OBJ_SOURCE.as_bytes()).unwrap();
+    // The triangle moves into the slot of the removed quad, and the other
+    // quad is split into triangles which inherit its tags
+    edited.mesh.remove_face(FaceIndex(2, 0));
+    edited.mesh.triangulate_all(Triangulation::Fan).unwrap();
+    let names = |obj: &obj::Obj, findex: FaceIndex| {
+        let tags = face_tags(&obj.mesh, findex);
+        (obj.groups[tags.group.unwrap()].clone(), obj.materials[tags.material.unwrap()].clone())
+    };
+    let expected: Vec<(String, String)> = edited.mesh.faces().map(|f| names(&edited, f)).collect();
+    assert_eq!(expected.iter().filter(|n| n.1 == "red").count(), 2);
+    assert_eq!(expected.iter().filter(|n| n.1 == "blue").count(), 1);
+
+    let mut output = Vec::new();
+    obj::write(&mut output, &edited).unwrap();
+    let copy = obj::read(&output[..]).unwrap();
+    let exported: Vec<(String, String)> = copy.mesh.faces().map(|f| names(&copy, f)).collect();
+    assert_eq!(exported, expected);
+}
+
+#[test]
+fn vertices_own_their_positions() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex_at(Position::new(1.0, 2.0, 3.0));
+    assert_eq!(mesh.position_list.len(), 3);
+    assert_eq!(*mesh.vertex_fn(v1).position(), Position::new(0.0, 0.0, 0.0));
+    assert_eq!(*mesh.vertex_fn(v2).position(), Position::new(1.0, 2.0, 3.0));
+
+    mesh.set_position(v1, Position::new(4.0, 5.0, 6.0));
+    assert_eq!(*mesh.position(v1), Position::new(4.0, 5.0, 6.0));
+    assert_eq!(mesh.position_list.len(), 3);
+
+    assert_eq!(mesh.try_add_vertex(Vertex { edge_index: EdgeIndex::default(), attr_index: 7, data: () }),
+               Err(Error::InvalidAttributeIndex(7)));
+    assert_eq!(mesh.try_set_position(VertexIndex(7, 0), Position::new(0.0, 0.0, 0.0)),
+               Err(Error::InvalidVertexIndex(VertexIndex(7, 0))));
+}
+
+#[test]
+fn removing_vertices_keeps_positions_consistent() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex_at(Position::new(1.0, 0.0, 0.0));
+    let v2 = mesh.add_vertex_at(Position::new(2.0, 0.0, 0.0));
+    let v3 = mesh.add_vertex_at(Position::new(3.0, 0.0, 0.0));
+    let v4 = mesh.add_vertex_at(Position::new(4.0, 0.0, 0.0));
+    mesh.add_triangle(v2, v3, v4);
+
+    assert!(mesh.remove_vertex(v1, RemovalPolicy::Refuse).is_ok());
+    assert_eq!(mesh.position_list.len(), 4);
+    // v4 now lives at the index previously used by v1
+    let v4 = mesh.vertex_index_at(v1.0);
+    assert_eq!(*mesh.position(v4), Position::new(4.0, 0.0, 0.0));
+    assert_eq!(*mesh.position(v2), Position::new(2.0, 0.0, 0.0));
+    assert_eq!(*mesh.position(v3), Position::new(3.0, 0.0, 0.0));
+
+    assert!(mesh.remove_vertex(v2, RemovalPolicy::Cascade).is_ok());
+    assert_eq!(mesh.position_list.len(), 3);
+    let v3 = mesh.vertex_index_at(v2.0);
+    assert_eq!(*mesh.position(v4), Position::new(4.0, 0.0, 0.0));
+    assert_eq!(*mesh.position(v3), Position::new(3.0, 0.0, 0.0));
+}
+
+#[test]
+fn attribute_channels_follow_their_components() {
+    let mut mesh = TestMesh::new();
+
+    let v1 = mesh.add_vertex(Vertex::default());
+    assert!(mesh.add_attribute::<u32>(AttributeDomain::Vertex, "id"));
+    assert!(!mesh.add_attribute::<u32>(AttributeDomain::Vertex, "id"));
+    assert_eq!(mesh.attribute::<u32>(AttributeDomain::Vertex, "id").unwrap().len(), 2);
+    assert!(mesh.attribute::<f32>(AttributeDomain::Vertex, "id").is_none());
+    assert!(mesh.attribute::<u32>(AttributeDomain::Face, "id").is_none());
+
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let v4 = mesh.add_vertex(Vertex::default());
+    {
+        let mut ids = mesh.attribute_mut::<u32>(AttributeDomain::Vertex, "id").unwrap();
+        assert_eq!(ids[v4], 0);
+        ids[v1] = 1;
+        ids[v2] = 2;
+        ids[v3] = 3;
+        ids[v4] = 4;
+    }
+
+    mesh.add_attribute::<u32>(AttributeDomain::Face, "id");
+    let f1 = mesh.add_triangle(v2, v3, v4);
+    let f2 = mesh.add_triangle(v4, v3, v2);
+    mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap()[f2] = 2;
+
+    assert!(mesh.remove_vertex(v1, RemovalPolicy::Refuse).is_ok());
+    let ids = mesh.attribute::<u32>(AttributeDomain::Vertex, "id").unwrap();
+    assert_eq!(ids.as_slice(), &[0, 4, 2, 3]);
+
+    mesh.remove_face(f1);
+    let ids = mesh.attribute::<u32>(AttributeDomain::Face, "id").unwrap();
+    assert_eq!(ids.as_slice(), &[0, 2]);
+    assert!(ids.get(f1).is_none());
+    assert_eq!(ids[mesh.face_index_at(f1.0)], 2);
+
+    assert!(mesh.remove_attribute(AttributeDomain::Face, "id"));
+    assert!(!mesh.has_attribute(AttributeDomain::Face, "id"));
+    assert!(mesh.has_attribute(AttributeDomain::Vertex, "id"));
+}
+
+#[test]
+fn undirected_edge_attributes_are_shared_by_twins() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.add_attribute::<f32>(AttributeDomain::UndirectedEdge, "crease");
+    mesh.add_attribute::<u8>(AttributeDomain::Edge, "tag");
+
+    let edge = mesh.face_fn(FaceIndex(1, 0)).edge().index;
+    let twin = mesh.edge(edge).twin_index;
+    mesh.set_undirected_edge_attribute("crease", edge, 2.0f32).unwrap();
+    mesh.attribute_mut::<u8>(AttributeDomain::Edge, "tag").unwrap()[edge] = 1;
+
+    let creases = mesh.attribute::<f32>(AttributeDomain::UndirectedEdge, "crease").unwrap();
+    assert_eq!(creases[edge], 2.0);
+    assert_eq!(creases[twin], 2.0);
+    assert_eq!(creases.iter().filter(|&&c| c != 0.0).count(), 2);
+    let tags = mesh.attribute::<u8>(AttributeDomain::Edge, "tag").unwrap();
+    assert_eq!(tags[twin], 0);
+
+    assert_eq!(mesh.set_undirected_edge_attribute("crease", edge, 1u8),
+               Err(Error::UnknownAttribute));
+    assert_eq!(mesh.set_undirected_edge_attribute("sharp", edge, 1.0f32),
+               Err(Error::UnknownAttribute));
+    assert_eq!(mesh.set_undirected_edge_attribute("crease", EdgeIndex(99, 0), 1.0f32),
+               Err(Error::InvalidEdgeIndex(EdgeIndex(99, 0))));
+
+    // the last edge takes the place of the removed one
+    let last = EdgeIndex(mesh.edge_list.len() - 1, 0);
+    mesh.attribute_mut::<u8>(AttributeDomain::Edge, "tag").unwrap()[last] = 7;
+    mesh.remove_edge(edge);
+    let tags = mesh.attribute::<u8>(AttributeDomain::Edge, "tag").unwrap();
+    assert_eq!(tags.len(), mesh.edge_list.len());
+    assert!(tags.get(edge).is_none());
+    assert_eq!(tags[mesh.edge_index_at(edge.0)], 7);
+}
+
+fn assert_close(a: Normal, b: Normal) {
+    use cgmath::InnerSpace;
+    assert!((a - b).magnitude() < 1e-5, "{:?} != {:?}", a, b);
+}
+
+#[test]
+fn can_compute_face_normals_of_polygons() {
+    let (mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    let center = Position::new(0.5, 0.5, 0.5);
+
+    for findex in mesh.faces() {
+        let face = mesh.face_fn(findex);
+        let normal = face.normal();
+        assert!((face.area() - 1.0).abs() < 1e-5);
+        let vindex = face.edge().vertex().index;
+        let outward = mesh.position(vindex) - center;
+        assert_eq!(normal.x.abs() + normal.y.abs() + normal.z.abs(), 1.0);
+        assert!(normal.x * outward.x + normal.y * outward.y + normal.z * outward.z > 0.0);
+    }
+    assert_close(mesh.face_fn(FaceIndex(1, 0)).normal(), Normal::new(0.0, 0.0, -1.0));
+
+    let mut mesh = TestMesh::new();
+    let v1 = mesh.add_vertex(Vertex::default());
+    let v2 = mesh.add_vertex(Vertex::default());
+    let v3 = mesh.add_vertex(Vertex::default());
+    let f1 = mesh.add_triangle(v1, v2, v3);
+    assert_eq!(mesh.face_fn(f1).normal(), Normal::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn vertex_normals_of_a_cube_point_away_from_its_center() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    let center = Position::new(0.5, 0.5, 0.5);
+
+    for &weighting in &[NormalWeighting::Uniform, NormalWeighting::Area, NormalWeighting::Angle] {
+        mesh.compute_vertex_normals(weighting);
+        let normals = mesh.attribute::<Normal>(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE).unwrap();
+        for i in 1 .. mesh.vertex_list.len() {
+            let expected = (mesh.position(VertexIndex(i, 0)) - center) * (2.0 / 3.0f32.sqrt());
+            assert_close(normals[VertexIndex(i, 0)], expected);
+        }
+    }
+}
+
+#[test]
+fn vertex_normal_weighting_modes_differ() {
+    let mut mesh = TestMesh::new();
+    mesh.add_attribute::<u8>(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE);
+
+    let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+    let v2 = mesh.add_vertex_at(Position::new(1.0, 0.0, 0.0));
+    let v3 = mesh.add_vertex_at(Position::new(0.0, 2.0, 0.0));
+    let v4 = mesh.add_vertex_at(Position::new(0.0, 0.0, 1.0));
+    let v5 = mesh.add_vertex(Vertex::default());
+    let mut builder = MeshBuilder::from_mesh(mesh);
+    builder.add_polygon(&[v1, v2, v3]).unwrap();
+    builder.add_polygon(&[v2, v1, v4]).unwrap();
+    let mut mesh = builder.build();
+
+    let normal = |mesh: &TestMesh, vindex| {
+        mesh.attribute::<Normal>(AttributeDomain::Vertex, VERTEX_NORMAL_ATTRIBUTE).unwrap()[vindex]
+    };
+
+    mesh.compute_vertex_normals(NormalWeighting::Uniform);
+    let sqrt_half = 0.5f32.sqrt();
+    assert_close(normal(&mesh, v1), Normal::new(0.0, sqrt_half, sqrt_half));
+    assert_close(normal(&mesh, v2), Normal::new(0.0, sqrt_half, sqrt_half));
+    assert_close(normal(&mesh, v3), Normal::new(0.0, 0.0, 1.0));
+    assert_close(normal(&mesh, v5), Normal::new(0.0, 0.0, 0.0));
+
+    mesh.compute_vertex_normals(NormalWeighting::Area);
+    let expected = Normal::new(0.0, 0.5, 1.0) / 1.25f32.sqrt();
+    assert_close(normal(&mesh, v1), expected);
+    assert_close(normal(&mesh, v2), expected);
+
+    mesh.compute_vertex_normals(NormalWeighting::Angle);
+    assert_close(normal(&mesh, v1), Normal::new(0.0, sqrt_half, sqrt_half));
+    let (a, b) = (::std::f32::consts::FRAC_PI_4, 2.0f32.atan());
+    let length = (a * a + b * b).sqrt();
+    assert_close(normal(&mesh, v2), Normal::new(0.0, a / length, b / length));
+}
+
+/// Two triangles sharing the edge from v1 to v2, forming a unit square.
+/// Two triangles sharing the edge from the first to the second vertex,
+/// forming a unit square.
+const SQUARE_POSITIONS: [[f32; 3]; 4] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [1.0, 0.0, 0.0],
+];
+
+const SQUARE_FACES: [[usize; 3]; 2] = [
+    [0, 1, 2],
+    [1, 0, 3],
+];
+
+#[test]
+fn can_flip_interior_edge() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3, v4) = (verts[0], verts[1], verts[2], verts[3]);
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let twin = mesh.edge(diagonal).twin_index;
+
+    assert!(mesh.flip_edge(diagonal).is_ok());
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert!(mesh.find_edge(v1, v2).is_none());
+    assert_eq!(mesh.find_edge(v3, v4), Some(diagonal));
+    assert_eq!(mesh.find_edge(v4, v3), Some(twin));
+    for findex in mesh.faces() {
+        assert_eq!(mesh.edges(mesh.face(findex)).count(), 3);
+        assert!((mesh.face_fn(findex).normal().z - 1.0).abs() < 1e-5);
+    }
+    assert_eq!(mesh.vertex_fn(v1).outgoing_edges().count(), 1);
+    assert_eq!(mesh.vertex_fn(v3).outgoing_edges().count(), 2);
+
+    assert!(mesh.flip_edge(twin).is_ok());
+    assert!(mesh.check_integrity().is_valid());
+    assert_eq!(mesh.find_edge(v1, v2), Some(twin));
+}
+
+#[test]
+fn flip_edge_refuses_invalid_flips() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3) = (verts[0], verts[1], verts[2]);
+    let boundary = mesh.find_edge(v2, v3).unwrap();
+    assert_eq!(mesh.flip_edge(boundary), Err(Error::BoundaryEdge(boundary)));
+    assert_eq!(mesh.flip_edge(EdgeIndex(42, 0)), Err(Error::InvalidEdgeIndex(EdgeIndex(42, 0))));
+    assert!(mesh.find_edge(v1, v2).is_some());
+
+    let (mut mesh, _, faces) = build_tetrahedron();
+    let edge = mesh.face_fn(faces[0]).edge().index;
+    let a = mesh.edge_fn(edge).prev().vertex().index;
+    let b = mesh.edge_fn(edge).twin().prev().vertex().index;
+    assert_eq!(mesh.flip_edge(edge), Err(Error::DuplicateEdge(a, b)));
+    assert!(mesh.check_integrity().is_valid());
+
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    assert_eq!(mesh.flip_edge(EdgeIndex(1, 0)), Err(Error::NonTriangularFace(FaceIndex(1, 0))));
+}
+
+#[test]
+fn flipping_an_edge_keeps_corner_attributes_at_their_corners() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    mesh.add_attribute::<usize>(AttributeDomain::Edge, "corner");
+    let edges: Vec<EdgeIndex> = mesh.faces()
+        .flat_map(|findex| mesh.edges(mesh.face(findex)).collect::<Vec<_>>())
+        .collect();
+    for &eindex in &edges {
+        let vindex = mesh.edge(eindex).vertex_index;
+        mesh.attribute_mut::<usize>(AttributeDomain::Edge, "corner").unwrap()[eindex] = vindex.0;
+    }
+
+    let diagonal = mesh.find_edge(verts[0], verts[1]).unwrap();
+    assert!(mesh.flip_edge(diagonal).is_ok());
+    let corners = mesh.attribute::<usize>(AttributeDomain::Edge, "corner").unwrap();
+    for eindex in edges {
+        assert_eq!(corners[eindex], mesh.edge(eindex).vertex_index.0);
+    }
+}
+
+#[test]
+fn can_split_edge_and_interpolate_attributes() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3, v4) = (verts[0], verts[1], verts[2], verts[3]);
+    mesh.add_attribute::<f32>(AttributeDomain::Vertex, "weight");
+    mesh.add_attribute::<f32>(AttributeDomain::Edge, "u");
+    mesh.add_attribute::<u8>(AttributeDomain::UndirectedEdge, "crease");
+    mesh.attribute_mut::<f32>(AttributeDomain::Vertex, "weight").unwrap().set_interpolation(linear_interpolation);
+    mesh.attribute_mut::<f32>(AttributeDomain::Edge, "u").unwrap().set_interpolation(linear_interpolation);
+    {
+        let mut weights = mesh.attribute_mut::<f32>(AttributeDomain::Vertex, "weight").unwrap();
+        weights[v1] = 1.0;
+        weights[v2] = 3.0;
+    }
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let twin = mesh.edge(diagonal).twin_index;
+    let (next, twin_next) = (mesh.edge(diagonal).next_index, mesh.edge(twin).next_index);
+    {
+        let mut u = mesh.attribute_mut::<f32>(AttributeDomain::Edge, "u").unwrap();
+        u[diagonal] = 0.0;
+        u[next] = 1.0;
+        u[twin] = 10.0;
+        u[twin_next] = 20.0;
+    }
+    mesh.set_undirected_edge_attribute("crease", diagonal, 1u8).unwrap();
+
+    let middle = mesh.split_edge(diagonal, 0.25).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(*mesh.position(middle), Position::new(0.25, 0.25, 0.0));
+    assert_eq!(mesh.attribute::<f32>(AttributeDomain::Vertex, "weight").unwrap()[middle], 1.5);
+    for findex in mesh.faces() {
+        assert_eq!(mesh.vertices(mesh.face(findex)).count(), 4);
+    }
+
+    let to_v2 = mesh.find_edge(middle, v2).unwrap();
+    let to_v1 = mesh.find_edge(middle, v1).unwrap();
+    assert_eq!(mesh.find_edge(v1, middle), Some(diagonal));
+    assert_eq!(mesh.find_edge(v2, middle), Some(twin));
+    assert_eq!(mesh.edge(diagonal).twin_index, to_v1);
+    assert_eq!(mesh.edge(twin).twin_index, to_v2);
+    let u = mesh.attribute::<f32>(AttributeDomain::Edge, "u").unwrap();
+    assert_eq!(u[to_v2], 0.25);
+    assert_eq!(u[to_v1], 17.5);
+    let creases = mesh.attribute::<u8>(AttributeDomain::UndirectedEdge, "crease").unwrap();
+    assert!([diagonal, twin, to_v1, to_v2].iter().all(|&e| creases[e] == 1));
+
+    let mut adjacent: Vec<VertexIndex> = mesh.vertex_fn(middle).adjacent_vertices().collect();
+    adjacent.sort_by_key(|v| v.0);
+    assert_eq!(adjacent, vec![v1, v2]);
+    assert!(mesh.vertex_fn(v3).is_boundary() && mesh.vertex_fn(v4).is_boundary());
+}
+
+#[test]
+fn can_split_edge_into_triangles() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3, v4) = (verts[0], verts[1], verts[2], verts[3]);
+    mesh.add_attribute::<u32>(AttributeDomain::Face, "id");
+    mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap()[FaceIndex::<usize>(2, 0)] = 2;
+
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let middle = mesh.split_edge_triangulated(diagonal, 0.5).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 4);
+    for findex in mesh.faces() {
+        assert_eq!(mesh.vertices(mesh.face(findex)).count(), 3);
+        assert!((mesh.face_fn(findex).normal().z - 1.0).abs() < 1e-5);
+    }
+    assert_eq!(mesh.vertex_fn(middle).outgoing_edges().count(), 4);
+    assert!(!mesh.vertex_fn(middle).is_boundary());
+    assert!(mesh.find_edge(middle, v3).is_some() && mesh.find_edge(v4, middle).is_some());
+    let ids = mesh.attribute::<u32>(AttributeDomain::Face, "id").unwrap();
+    assert_eq!(ids.iter().filter(|&&id| id == 2).count(), 2);
+
+    let boundary = mesh.find_edge(v2, v3).unwrap();
+    let middle = mesh.split_edge_triangulated(boundary, 0.5).unwrap();
+    assert!(mesh.check_integrity().is_valid());
+    assert_eq!(mesh.faces().count(), 5);
+    assert!(mesh.vertex_fn(middle).is_boundary());
+    assert_eq!(mesh.vertex_fn(middle).adjacent_vertices().count(), 3);
+}
+
+const OCTAHEDRON_POSITIONS: [[f32; 3]; 6] = [
+    [1.0, 0.0, 0.0],
+    [-1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, -1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [0.0, 0.0, -1.0],
+];
+
+const OCTAHEDRON_FACES: [[usize; 3]; 8] = [
+    [0, 2, 4], [2, 1, 4], [1, 3, 4], [3, 0, 4],
+    [2, 0, 5], [1, 2, 5], [3, 1, 5], [0, 3, 5],
+];
+
+#[test]
+fn can_collapse_edges_of_closed_mesh() {
+    let (mut mesh, _, _) = build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES);
+    mesh.add_attribute::<f32>(AttributeDomain::UndirectedEdge, "crease");
+    for &(from, to, crease) in &[(1, 5, 2.0f32), (3, 5, 1.0), (3, 6, 3.0)] {
+        let edge = mesh.find_edge(VertexIndex(from, 0), VertexIndex(to, 0)).unwrap();
+        mesh.set_undirected_edge_attribute("crease", edge, crease).unwrap();
+    }
+    let edge = mesh.find_edge(VertexIndex(1, 0), VertexIndex(3, 0)).unwrap();
+    let merged = mesh.collapse_edge(edge, Position::new(0.5, 0.5, 0.0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.vertex_list.len(), 6);
+    assert_eq!(mesh.edge_list.len(), 19);
+    assert_eq!(mesh.faces().count(), 6);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+    assert_eq!(*mesh.position(merged), Position::new(0.5, 0.5, 0.0));
+    assert_eq!(mesh.vertex_fn(merged).adjacent_vertices().count(), 4);
+
+    // The edges merged in place of each removed triangle keep the creases
+    // of the ones at the merged vertex.
+    let kept = mesh.find_edge(merged, VertexIndex(5, 0)).unwrap();
+    let creases = mesh.attribute::<f32>(AttributeDomain::UndirectedEdge, "crease").unwrap();
+    assert_eq!(creases[kept], 2.0);
+    assert_eq!(creases[mesh.edge(kept).twin_index], 2.0);
+    assert_eq!(creases.iter().filter(|&&c| c != 0.0).count(), 2);
+
+    // Collapsing an edge of the remaining equator would pinch the mesh
+    let equator = mesh.find_edge(merged, VertexIndex(2, 0)).unwrap();
+    assert_eq!(mesh.collapse_edge(equator, Position::new(0.0, 0.0, 0.0)),
+               Err(Error::LinkCondition(equator)));
+
+    let edge = mesh.find_edge(merged, VertexIndex(5, 0)).unwrap();
+    let merged = mesh.collapse_edge(edge, Position::new(0.0, 0.0, 0.0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.vertex_list.len(), 5);
+    assert_eq!(mesh.faces().count(), 4);
+    assert_eq!(mesh.vertex_fn(merged).adjacent_vertices().count(), 3);
+
+    // A tetrahedron can't be collapsed any further
+    let edge = mesh.vertex_fn(merged).edge().index;
+    assert_eq!(mesh.collapse_edge(edge, Position::new(0.0, 0.0, 0.0)),
+               Err(Error::LinkCondition(edge)));
+    assert_eq!(mesh.faces().count(), 4);
+}
+
+#[test]
+fn can_collapse_edges_near_boundaries() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3) = (verts[0], verts[1], verts[2]);
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let middle = mesh.split_edge_triangulated(diagonal, 0.5).unwrap();
+
+    let edge = mesh.find_edge(middle, v1).unwrap();
+    let merged = mesh.collapse_edge(edge, Position::new(0.0, 0.0, 0.0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 2);
+    assert_eq!(mesh.vertex_list.len(), 5);
+    assert_eq!(mesh.vertex_fn(merged).adjacent_vertices().count(), 3);
+    assert!(mesh.vertex_fn(merged).is_boundary());
+
+    // Both ends of the interior diagonal are on the boundary
+    let (mut mesh, _, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    assert_eq!(mesh.collapse_edge(diagonal, Position::new(0.0, 0.0, 0.0)),
+               Err(Error::LinkCondition(diagonal)));
+
+    let boundary = mesh.find_edge(v2, v3).unwrap();
+    let merged = mesh.collapse_edge(boundary, Position::new(0.5, 1.0, 0.0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 1);
+    let mut vertices: Vec<VertexIndex> = mesh.vertices(mesh.face(mesh.face_index_at(1))).collect();
+    vertices.sort_by_key(|v| v.0);
+    // v4 has taken over the index of the removed v3
+    assert_eq!(vertices, vec![v1, merged, mesh.vertex_index_at(v3.0)]);
+}
+
+#[test]
+fn can_dissolve_edges() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.add_attribute::<u32>(AttributeDomain::Face, "id");
+    for i in 1 .. mesh.face_list.len() {
+        mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap()[FaceIndex(i, 0)] = i as u32;
+    }
+
+    let edge = mesh.find_edge(VertexIndex(2, 0), VertexIndex(3, 0)).unwrap();
+    let twin_face = mesh.edge_fn(edge).twin().face().index;
+    let merged = mesh.dissolve_edge(edge).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(merged, FaceIndex(1, 0));
+    assert_eq!(twin_face, merged);
+    assert_eq!(mesh.faces().count(), 5);
+    assert_eq!(mesh.edge_list.len(), 23);
+    assert_eq!(mesh.vertices(mesh.face(merged)).count(), 6);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+    assert!(mesh.find_edge(VertexIndex(2, 0), VertexIndex(3, 0)).is_none());
+    let ids = mesh.attribute::<u32>(AttributeDomain::Face, "id").unwrap();
+    assert_eq!(ids.as_slice(), &[0, 1, 2, 3, 6, 5]);
+
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3) = (verts[0], verts[1], verts[2]);
+    let boundary = mesh.find_edge(v2, v3).unwrap();
+    assert_eq!(mesh.dissolve_edge(boundary), Err(Error::BoundaryEdge(boundary)));
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let quad = mesh.dissolve_edge(diagonal).unwrap();
+    let edge = mesh.face(quad).edge_index;
+    mesh.set_twin_edges(edge, mesh.edge(edge).next_index);
+    assert_eq!(mesh.dissolve_edge(edge), Err(Error::BridgeEdge(edge)));
+}
+
+#[test]
+fn can_dissolve_interior_vertices() {
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3, v4) = (verts[0], verts[1], verts[2], verts[3]);
+    let diagonal = mesh.find_edge(v1, v2).unwrap();
+    let middle = mesh.split_edge_triangulated(diagonal, 0.5).unwrap();
+    assert_eq!(mesh.faces().count(), 4);
+
+    let merged = mesh.dissolve_vertex(middle).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 1);
+    assert_eq!(mesh.vertex_list.len(), 5);
+    assert_eq!(mesh.edge_list.len(), 5);
+    let vertices: Vec<VertexIndex> = mesh.vertices(mesh.face(merged)).collect();
+    assert_eq!(vertices.len(), 4);
+    assert!([v1, v2, v3, v4].iter().all(|v| vertices.contains(v)));
+
+    let (mut mesh, _, _) = build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES);
+    let merged = mesh.dissolve_vertex(VertexIndex(5, 0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 5);
+    assert_eq!(mesh.vertices(mesh.face(merged)).count(), 4);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+
+    let mut mesh = TestMesh::new();
+    let v1 = mesh.add_vertex(Vertex::default());
+    assert_eq!(mesh.dissolve_vertex(v1), Err(Error::IsolatedVertex(v1)));
+}
+
+#[test]
+fn can_dissolve_faces_and_boundary_vertices() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    assert!(mesh.dissolve_face(FaceIndex(1, 0)).is_ok());
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 5);
+    assert_eq!(mesh.edge_list.len(), 21);
+    assert_eq!(mesh.edge_list[1..].iter().filter(|e| e.is_boundary()).count(), 4);
+    for index in 1 .. 5 {
+        let vertex = mesh.vertex_fn(VertexIndex(index, 0));
+        assert!(vertex.is_boundary());
+        assert_eq!(vertex.outgoing_edges().count(), 2);
+        assert_eq!(vertex.adjacent_vertices().count(), 3);
+    }
+
+    mesh.add_attribute::<f32>(AttributeDomain::UndirectedEdge, "crease");
+    let edges: Vec<_> = (1 .. mesh.edge_list.len()).map(|offset| mesh.edge_index_at(offset)).collect();
+    let mut creases = mesh.attribute_mut::<f32>(AttributeDomain::UndirectedEdge, "crease").unwrap();
+    for &edge in &edges {
+        creases[edge] = 3.0;
+    }
+
+    let merged = mesh.dissolve_vertex(VertexIndex(1, 0)).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 4);
+    assert_eq!(mesh.vertices(mesh.face(merged)).count(), 5);
+    assert_eq!(mesh.edge_list[1..].iter().filter(|e| e.is_boundary()).count(), 3);
+    // The edge closing the merged face takes its crease from the spokes
+    let creases = mesh.attribute::<f32>(AttributeDomain::UndirectedEdge, "crease").unwrap();
+    assert!(creases.as_slice()[1 .. mesh.edge_list.len()].iter().all(|&crease| crease == 3.0));
+
+    // A single triangle would be left with two edges
+    let (mut mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let v3 = verts[2];
+    assert_eq!(mesh.dissolve_vertex(v3), Err(Error::DegeneratePolygon));
+    assert_eq!(mesh.dissolve_face(FaceIndex(3, 0)), Err(Error::InvalidFaceIndex(FaceIndex(3, 0))));
+    assert!(mesh.dissolve_face(FaceIndex(1, 0)).is_ok());
+    assert!(mesh.check_integrity().is_valid());
+    assert!(!mesh.vertex(v3).is_valid());
+    assert!(mesh.edge_list[1..].iter().all(|e| e.is_boundary()));
+}
+
+#[test]
+fn can_split_faces_between_vertices() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.add_attribute::<u32>(AttributeDomain::Face, "id");
+    mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap()[FaceIndex::<usize>(2, 0)] = 7;
+
+    // Face 2 is the loop 5, 6, 7, 8
+    let (v5, v7) = (VertexIndex(5, 0), VertexIndex(7, 0));
+    let new_face = mesh.split_face(FaceIndex(2, 0), v5, v7).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 7);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+    let diagonal = mesh.find_edge(v5, v7).unwrap();
+    assert_eq!(mesh.edge(diagonal).face_index, FaceIndex(2, 0));
+    assert_eq!(mesh.edge_fn(diagonal).twin().face().index, new_face);
+    let new_vertices: Vec<VertexIndex> = mesh.vertices(mesh.face(new_face)).collect();
+    assert_eq!(new_vertices, vec![v5, VertexIndex(6, 0), v7]);
+    for &findex in &[FaceIndex(2, 0), new_face] {
+        assert_eq!(mesh.attribute::<u32>(AttributeDomain::Face, "id").unwrap()[findex], 7);
+        assert_close(mesh.face_fn(findex).normal(), Normal::new(0.0, 0.0, 1.0));
+    }
+
+    assert_eq!(mesh.split_face(FaceIndex(1, 0), VertexIndex(1, 0), VertexIndex(2, 0)),
+               Err(Error::DuplicateEdge(VertexIndex(1, 0), VertexIndex(2, 0))));
+    assert_eq!(mesh.split_face(FaceIndex(1, 0), VertexIndex(1, 0), VertexIndex(5, 0)),
+               Err(Error::VertexNotInFace(VertexIndex(5, 0), FaceIndex(1, 0))));
+    assert_eq!(mesh.split_face(FaceIndex(1, 0), VertexIndex(1, 0), VertexIndex(1, 0)),
+               Err(Error::DegeneratePolygon));
+    assert_eq!(mesh.split_face(new_face, v5, v7),
+               Err(Error::DuplicateEdge(v5, v7)));
+}
+
+#[test]
+fn can_triangulate_closed_mesh() {
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.add_attribute::<usize>(AttributeDomain::Edge, "corner");
+    for i in 1 .. mesh.edge_list.len() {
+        let vindex = mesh.edge(EdgeIndex(i, 0)).vertex_index;
+        mesh.attribute_mut::<usize>(AttributeDomain::Edge, "corner").unwrap()[EdgeIndex(i, 0)] = vindex.0;
+    }
+    let normals: Vec<Normal> = mesh.faces().map(|f| mesh.face_fn(f).normal()).collect();
+
+    let triangles = mesh.triangulate_face(FaceIndex(1, 0), Triangulation::Fan).unwrap();
+    assert_eq!(triangles, vec![FaceIndex(1, 0), FaceIndex(7, 0)]);
+    assert!(mesh.triangulate_all(Triangulation::EarClipping).is_ok());
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(mesh.faces().count(), 12);
+    assert_eq!(mesh.edge_list.len(), 37);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+    for findex in mesh.faces() {
+        assert_eq!(mesh.vertices(mesh.face(findex)).count(), 3);
+        assert!((mesh.face_fn(findex).area() - 0.5).abs() < 1e-5);
+    }
+    for (i, &normal) in normals.iter().enumerate() {
+        assert_close(mesh.face_fn(FaceIndex(i + 1, 0)).normal(), normal);
+    }
+    let corners = mesh.attribute::<usize>(AttributeDomain::Edge, "corner").unwrap();
+    for i in 1 .. mesh.edge_list.len() {
+        assert_eq!(corners[EdgeIndex(i, 0)], mesh.edge(EdgeIndex(i, 0)).vertex_index.0);
+    }
+}
+
+#[test]
+fn ear_clipping_handles_concave_polygons() {
+    let build = || {
+        let mut mesh = TestMesh::new();
+        let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+        let v2 = mesh.add_vertex_at(Position::new(2.0, 0.0, 0.0));
+        let v3 = mesh.add_vertex_at(Position::new(1.0, 1.0, 0.0));
+        let v4 = mesh.add_vertex_at(Position::new(2.0, 2.0, 0.0));
+        let v5 = mesh.add_vertex_at(Position::new(0.0, 2.0, 0.0));
+        let face = mesh.add_polygon(&[v4, v5, v1, v2, v3]);
+        (mesh, face)
+    };
+
+    // A fan from the tip of the notch folds over the reflex corner
+    let (mut mesh, face) = build();
+    let triangles = mesh.triangulate_face(face, Triangulation::Fan).unwrap();
+    assert!(triangles.iter().any(|&f| mesh.face_fn(f).normal().z < 0.0));
+
+    let (mut mesh, face) = build();
+    let triangles = mesh.triangulate_face(face, Triangulation::EarClipping).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(triangles.len(), 3);
+    let mut area = 0.0;
+    for &findex in &triangles {
+        assert_eq!(mesh.vertices(mesh.face(findex)).count(), 3);
+        assert_close(mesh.face_fn(findex).normal(), Normal::new(0.0, 0.0, 1.0));
+        area += mesh.face_fn(findex).area();
+    }
+    assert!((area - 3.0).abs() < 1e-5);
+}
+
+#[test]
+fn loop_subdivision_uses_interior_stencils() {
+    let (mesh, _, _) = build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES);
+    let result = subdivide::loop_subdivision(&mesh, 1).unwrap();
+    let report = result.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(result.vertex_list.len(), 1 + 6 + 12);
+    assert_eq!(result.faces().count(), 32);
+    assert!(result.edge_list[1..].iter().all(|e| !e.is_boundary()));
+
+    // Valence four vertices move by 31/256 of their neighbours each
+    assert_eq!(*result.position(VertexIndex(1, 0)), Position::new(0.515625, 0.0, 0.0));
+    let edge_point = (7 .. result.vertex_list.len())
+        .map(|i| *result.position(VertexIndex(i, 0)))
+        .find(|p| p.x > 0.0 && p.y > 0.0);
+    assert_eq!(edge_point, Some(Position::new(0.375, 0.375, 0.0)));
+
+    let result = subdivide::loop_subdivision(&mesh, 2).unwrap();
+    assert!(result.check_integrity().is_valid());
+    assert_eq!(result.faces().count(), 128);
+
+    let result = subdivide::loop_subdivision(&mesh, 0).unwrap();
+    assert_eq!(result.faces().count(), 8);
+    assert_eq!(*result.position(VertexIndex(1, 0)), Position::new(1.0, 0.0, 0.0));
+
+    assert_eq!(subdivide::loop_subdivision(&build_mesh(&CUBE_POSITIONS, &CUBE_FACES).0, 1).unwrap_err(),
+               Error::NonTriangularFace(FaceIndex(1, 0)));
+}
+
+#[test]
+fn loop_subdivision_uses_boundary_rules() {
+    let (mesh, verts, _) = build_mesh(&SQUARE_POSITIONS, &SQUARE_FACES);
+    let (v1, v2, v3, v4) = (verts[0], verts[1], verts[2], verts[3]);
+    let result = subdivide::loop_subdivision(&mesh, 1).unwrap();
+    let report = result.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(result.faces().count(), 8);
+    assert_eq!(result.edge_list[1..].iter().filter(|e| e.is_boundary()).count(), 8);
+
+    // Corners only see their boundary neighbours
+    assert_eq!(*result.position(v1), Position::new(0.125, 0.125, 0.0));
+    assert_eq!(*result.position(v2), Position::new(0.875, 0.875, 0.0));
+    assert_eq!(*result.position(v3), Position::new(0.125, 0.875, 0.0));
+    assert_eq!(*result.position(v4), Position::new(0.875, 0.125, 0.0));
+    // Boundary edge points are midpoints, interior ones use the full stencil
+    let points: Vec<Position> = (5 .. result.vertex_list.len())
+        .map(|i| *result.position(VertexIndex(i, 0)))
+        .collect();
+    assert!(points.contains(&Position::new(0.5, 1.0, 0.0)));
+    assert!(points.contains(&Position::new(0.5, 0.5, 0.0)));
+}
+
+#[test]
+fn catmull_clark_subdivides_closed_polygon_meshes() {
+    let (mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    let result = subdivide::catmull_clark(&mesh, 1).unwrap();
+    let report = result.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(result.vertex_list.len(), 1 + 8 + 12 + 6);
+    assert_eq!(result.faces().count(), 24);
+    assert!(result.edge_list[1..].iter().all(|e| !e.is_boundary()));
+    assert!(!result.has_attribute(AttributeDomain::UndirectedEdge, subdivide::CREASE_ATTRIBUTE));
+
+    // Corners of a unit cube move 4/9 of the way towards its center
+    let corner = Position::new(2.0 / 9.0, 2.0 / 9.0, 2.0 / 9.0);
+    assert_close(*result.position(VertexIndex(1, 0)) - corner, Normal::new(0.0, 0.0, 0.0));
+    // Face points are face centers
+    assert_eq!(*result.position(VertexIndex(21, 0)), Position::new(0.5, 0.5, 0.0));
+
+    // Triangles become three quads each
+    let result = subdivide::catmull_clark(&build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES).0, 2).unwrap();
+    assert!(result.check_integrity().is_valid());
+    assert_eq!(result.faces().count(), 8 * 3 * 4);
+}
+
+#[test]
+fn catmull_clark_keeps_boundaries_and_creases_sharp() {
+    let quad: &[usize] = &[0, 1, 2, 3];
+    let mut mesh = TestMesh::from_indexed_faces(4, &[quad]).unwrap();
+    for (i, &(x, y)) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].iter().enumerate() {
+        mesh.set_position(VertexIndex(i + 1, 0), Position::new(x, y, 0.0));
+    }
+    let result = subdivide::catmull_clark(&mesh, 1).unwrap();
+    assert!(result.check_integrity().is_valid());
+    assert_eq!(result.faces().count(), 4);
+    assert_eq!(*result.position(VertexIndex(1, 0)), Position::new(0.125, 0.125, 0.0));
+    assert_eq!(*result.position(VertexIndex(5, 0)), Position::new(0.5, 0.0, 0.0));
+
+    // Sharpen the four edges around the bottom face of the cube
+    let (mut mesh, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.add_attribute::<f32>(AttributeDomain::UndirectedEdge, subdivide::CREASE_ATTRIBUTE);
+    let edges: Vec<EdgeIndex> = mesh.edges(mesh.face(FaceIndex(1, 0))).collect();
+    for &eindex in &edges {
+        mesh.set_undirected_edge_attribute(subdivide::CREASE_ATTRIBUTE, eindex, 2.0f32).unwrap();
+    }
+    let result = subdivide::catmull_clark(&mesh, 1).unwrap();
+    assert!(result.check_integrity().is_valid());
+    // Crease vertices follow the crease rule, crease edge points are midpoints
+    assert_eq!(*result.position(VertexIndex(1, 0)), Position::new(0.125, 0.125, 0.0));
+    let creases = result.attribute::<f32>(AttributeDomain::UndirectedEdge, subdivide::CREASE_ATTRIBUTE).unwrap();
+    assert_eq!(creases.iter().filter(|&&s| s == 1.0).count(), 16);
+    let neighbours: Vec<Position> = result.vertex_fn(VertexIndex(1, 0)).adjacent_vertices()
+        .map(|v| *result.position(v))
+        .collect();
+    assert!(neighbours.contains(&Position::new(0.5, 0.0, 0.0)));
+    assert!(neighbours.contains(&Position::new(0.0, 0.5, 0.0)));
+
+    // The next level uses up the remaining sharpness
+    let result = subdivide::catmull_clark(&result, 1).unwrap();
+    assert!(result.check_integrity().is_valid());
+    let creases = result.attribute::<f32>(AttributeDomain::UndirectedEdge, subdivide::CREASE_ATTRIBUTE).unwrap();
+    assert!(creases.iter().all(|&s| s == 0.0));
+}
+
+#[test]
+fn decimation_keeps_closed_meshes_valid() {
+    let mut mesh = subdivide::loop_subdivision(&build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES).0, 2).unwrap();
+    assert_eq!(mesh.faces().count(), 128);
+
+    let face_count = simplify::decimate(&mut mesh, 32);
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert_eq!(face_count, mesh.faces().count());
+    assert_eq!(face_count, 32);
+    assert!(mesh.edge_list[1..].iter().all(|e| !e.is_boundary()));
+
+    // The mesh stays convex around the origin, so no face was turned over
+    for findex in mesh.faces() {
+        let face = mesh.face_fn(findex);
+        let (n, p) = (face.normal(), mesh.position(face.edge().vertex().index));
+        assert!(n.x * p.x + n.y * p.y + n.z * p.z > 0.0);
+    }
+
+    // Asking for more faces than there are does nothing
+    assert_eq!(simplify::decimate(&mut mesh, 100), 32);
+    assert_eq!(mesh.faces().count(), 32);
+}
+
+/// The positions and faces of a flat unit square made of 4x4 quads split
+/// into triangles, with the vertex at (x, y) stored at offset `y * 5 + x`.
+fn grid() -> (Vec<[f32; 3]>, Vec<[usize; 3]>) {
+    let positions = (0 .. 25)
+        .map(|i| [(i % 5) as f32 * 0.25, (i / 5) as f32 * 0.25, 0.0])
+        .collect();
+    let mut faces = Vec::new();
+    for y in 0 .. 4 {
+        for x in 0 .. 4 {
+            let i = y * 5 + x;
+            faces.push([i, i + 1, i + 6]);
+            faces.push([i, i + 6, i + 5]);
+        }
+    }
+    (positions, faces)
+}
+
+#[test]
+fn decimation_preserves_boundaries() {
+    let (positions, faces) = grid();
+    let (mut mesh, _, _) = build_mesh(&positions, &faces);
+    let face_count = simplify::decimate(&mut mesh, 8);
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+    assert!(face_count <= 8);
+
+    for edge in mesh.edge_list[1..].iter().filter(|e| e.is_boundary()) {
+        let p = mesh.position(edge.vertex_index);
+        assert!(p.x == 0.0 || p.x == 1.0 || p.y == 0.0 || p.y == 1.0, "{:?}", p);
+    }
+    let corners = (1 .. mesh.vertex_list.len())
+        .map(|i| mesh.position(mesh.vertex_index_at(i)))
+        .filter(|p| (p.x == 0.0 || p.x == 1.0) && (p.y == 0.0 || p.y == 1.0))
+        .count();
+    assert_eq!(corners, 4);
+}
+
+#[test]
+fn taubin_smoothing_shrinks_less_than_laplacian() {
+    let radius = |mesh: &TestMesh| (1 .. mesh.vertex_list.len())
+        .map(|i| mesh.position(VertexIndex(i, 0)))
+        .map(|p| (p.x * p.x + p.y * p.y + p.z * p.z).sqrt())
+        .fold(0.0f32, f32::max);
+    let build = || subdivide::loop_subdivision(&build_mesh(&OCTAHEDRON_POSITIONS, &OCTAHEDRON_FACES).0, 2).unwrap();
+    let mesh = build();
+    let options = smooth::Smoothing { iterations: 10, ..smooth::Smoothing::default() };
+
+    let mut laplacian = build();
+    smooth::laplacian(&mut laplacian, 0.5, options).unwrap();
+    let mut taubin = build();
+    smooth::taubin(&mut taubin, 0.5, -0.53, options).unwrap();
+    assert!(radius(&laplacian) < radius(&mesh));
+    assert!(radius(&laplacian) < radius(&taubin));
+    assert!(taubin.check_integrity().is_valid());
+}
+
+#[test]
+fn cotangent_smoothing_keeps_flat_meshes_flat() {
+    let center = VertexIndex(13, 0);
+    let build = || {
+        let (positions, faces) = grid();
+        let (mut mesh, _, _) = build_mesh(&positions, &faces);
+        mesh.set_position(center, Position::new(0.6, 0.55, 0.0));
+        mesh
+    };
+    let options = smooth::Smoothing {
+        weights: smooth::LaplacianWeights::Cotangent,
+        ..smooth::Smoothing::default()
+    };
+
+    // Cotangent weights have no tangential component on a plane
+    let mut cotangent = build();
+    smooth::laplacian(&mut cotangent, 1.0, options).unwrap();
+    let p = cotangent.position(center);
+    assert!((p.x - 0.6).abs() < 1e-5 && (p.y - 0.55).abs() < 1e-5, "{:?}", p);
+
+    let mut uniform = build();
+    smooth::laplacian(&mut uniform, 1.0, smooth::Smoothing::default()).unwrap();
+    assert!(uniform.position(center).x < 0.6);
+
+    // Lifted vertices are pulled back into the plane
+    let (positions, faces) = grid();
+    let (mut mesh, _, _) = build_mesh(&positions, &faces);
+    mesh.set_position(center, Position::new(0.5, 0.5, 1.0));
+    smooth::laplacian(&mut mesh, 1.0, options).unwrap();
+    assert_eq!(*mesh.position(center), Position::new(0.5, 0.5, 0.0));
+}
+
+#[test]
+fn smoothing_respects_pinning_and_selection() {
+    let (corner, inner, center) = (VertexIndex(1, 0), VertexIndex(7, 0), VertexIndex(13, 0));
+    let build = || {
+        let (positions, faces) = grid();
+        let (mut mesh, _, _) = build_mesh(&positions, &faces);
+        mesh.set_position(center, Position::new(0.5, 0.5, 1.0));
+        mesh.set_position(inner, Position::new(0.25, 0.25, 1.0));
+        mesh
+    };
+
+    let mut unpinned = build();
+    let options = smooth::Smoothing { pin_boundary: false, ..smooth::Smoothing::default() };
+    smooth::laplacian(&mut unpinned, 1.0, options).unwrap();
+    assert!(*unpinned.position(corner) != Position::new(0.0, 0.0, 0.0));
+
+    let mut mesh = build();
+    let options = smooth::Smoothing { selection: Some("selected"), ..smooth::Smoothing::default() };
+    assert_eq!(smooth::laplacian(&mut mesh, 1.0, options), Err(Error::UnknownAttribute));
+    mesh.add_attribute::<u8>(AttributeDomain::Vertex, "selected");
+    assert_eq!(smooth::taubin(&mut mesh, 0.5, -0.53, options), Err(Error::UnknownAttribute));
+    assert_eq!(*mesh.position(center), Position::new(0.5, 0.5, 1.0));
+
+    mesh.remove_attribute(AttributeDomain::Vertex, "selected");
+    mesh.add_attribute::<bool>(AttributeDomain::Vertex, "selected");
+    mesh.attribute_mut::<bool>(AttributeDomain::Vertex, "selected").unwrap()[center] = true;
+    smooth::laplacian(&mut mesh, 1.0, options).unwrap();
+    assert_eq!(*mesh.position(corner), Position::new(0.0, 0.0, 0.0));
+    assert_eq!(*mesh.position(inner), Position::new(0.25, 0.25, 1.0));
+    assert!(mesh.position(center).z < 1.0);
+}
+
+#[test]
+fn stale_indices_are_detected() {
+    let (mut mesh, verts, faces) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    let last_edge = mesh.edge_index_at(mesh.edge_list.len() - 1);
+    mesh.add_attribute::<u32>(AttributeDomain::Face, "id");
+    mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap()[faces[5]] = 5;
+
+    // faces[5] moves into the slot of faces[1]
+    mesh.remove_face(faces[1]);
+    let moved = mesh.face_index_at(faces[1].0);
+    assert!(!mesh.is_current_face(faces[1]));
+    assert!(!mesh.is_current_face(faces[5]));
+    assert!(mesh.is_current_face(moved));
+    assert!(!mesh.face(faces[1]).is_valid());
+    assert!(!mesh.face_fn(faces[5]).is_valid());
+    assert!(mesh.get_face(faces[1]).is_none());
+    assert!(mesh.get_face_fn(faces[5]).is_none());
+    assert!(mesh.face_mut(faces[5]).is_none());
+    assert!(mesh.get_face(moved).is_some());
+    assert_eq!(mesh.try_remove_face(faces[1]), Err(Error::InvalidFaceIndex(faces[1])));
+    assert!(mesh.face_fn(moved).edge().face().index == moved);
+
+    // Attribute values follow the moved face, not the stale indices
+    {
+        let mut ids = mesh.attribute_mut::<u32>(AttributeDomain::Face, "id").unwrap();
+        assert!(ids.get(faces[5]).is_none());
+        assert!(ids.get_mut(faces[1]).is_none());
+        assert_eq!(ids[moved], 5);
+    }
+
+    // A new face in the vacated slot doesn't revive the old index
+    let f7 = mesh.add_face(Face::default());
+    assert_eq!(f7.0, faces[5].0);
+    assert!(f7 != faces[5]);
+    assert!(!mesh.is_current_face(faces[5]));
+
+    let eindex = mesh.face_fn(faces[0]).edge().index;
+    mesh.remove_edge(eindex);
+    assert!(!mesh.edge(eindex).is_valid());
+    assert!(!mesh.edge_fn(last_edge).is_valid());
+    assert!(mesh.get_edge(eindex).is_none());
+    assert!(mesh.get_edge_fn(last_edge).is_none());
+    assert!(mesh.edge_mut(last_edge).is_none());
+
+    assert!(mesh.remove_vertex(verts[0], RemovalPolicy::Cascade).is_ok());
+    assert!(!mesh.is_current_vertex(verts[0]));
+    assert!(!mesh.is_current_vertex(verts[7]));
+    assert!(mesh.get_vertex(verts[7]).is_none());
+    assert!(mesh.get_vertex_fn(verts[0]).is_none());
+    assert!(mesh.get_vertex(VertexIndex::default()).is_none());
+    assert_eq!(mesh.find_edge(verts[7], verts[6]), None);
+    assert_eq!(mesh.try_set_position(verts[7], Position::new(1.0, 0.0, 0.0)),
+               Err(Error::InvalidVertexIndex(verts[7])));
+
+    // The removals left holes, but every link was updated
+    let report = mesh.check_integrity();
+    assert!(!report.violations.iter().any(|v| matches!(v, Violation::StaleIndex(_))), "{:?}", report);
+}
+
+#[test]
+fn tombstone_removal_keeps_indices_stable() {
+    let (mut mesh, verts, faces) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.set_removal_mode(RemovalMode::Tombstone);
+    let remaining: Vec<FaceIndex> = faces.iter()
+        .cloned()
+        .filter(|&f| !mesh.vertices(mesh.face(f)).any(|v| v == verts[0]))
+        .collect();
+    assert_eq!(remaining.len(), 3);
+
+    assert!(mesh.remove_vertex(verts[0], RemovalPolicy::Cascade).is_ok());
+    assert_eq!(mesh.vertex_list.len(), 9);
+    assert_eq!((mesh.vertex_count(), mesh.edge_count(), mesh.face_count()), (7, 18, 3));
+    assert!(!mesh.is_current_vertex(verts[0]));
+    assert!(verts[1 ..].iter().all(|&v| mesh.is_current_vertex(v)));
+    assert_eq!(mesh.faces().collect::<Vec<_>>(), remaining);
+
+    for &face in &remaining {
+        assert!(mesh.vertices(mesh.face(face)).all(|v| verts[1 ..].contains(&v)));
+    }
+
+    // The twins of the removed edges are left dangling, as with `swap_remove`
+    let report = mesh.check_integrity();
+    assert!(!report.violations.iter().any(|v| matches!(v, Violation::StaleIndex(_))), "{:?}", report);
+}
+
+#[test]
+fn decimation_works_with_tombstones() {
+    let (mut cube, _, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    cube.triangulate_all(Triangulation::Fan).unwrap();
+    let mut mesh = subdivide::loop_subdivision(&cube, 2).unwrap();
+    mesh.set_removal_mode(RemovalMode::Tombstone);
+    let vertex_slots = mesh.vertex_list.len();
+
+    assert_eq!(simplify::decimate(&mut mesh, 32), 32);
+    assert_eq!(mesh.vertex_list.len(), vertex_slots);
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+
+    mesh.compact();
+    assert_eq!(mesh.vertex_list.len(), mesh.vertex_count() + 1);
+    assert_eq!(mesh.edge_list.len(), mesh.edge_count() + 1);
+    assert_eq!(mesh.face_list.len(), 33);
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn compaction_remaps_indices_and_attributes() {
+    let (mut mesh, verts, faces) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.set_removal_mode(RemovalMode::Tombstone);
+    mesh.add_attribute::<usize>(AttributeDomain::Vertex, "id");
+    mesh.add_attribute::<usize>(AttributeDomain::Face, "id");
+    for &v in &verts {
+        mesh.attribute_mut::<usize>(AttributeDomain::Vertex, "id").unwrap()[v] = v.0;
+    }
+    for &f in &faces {
+        mesh.attribute_mut::<usize>(AttributeDomain::Face, "id").unwrap()[f] = f.0;
+    }
+
+    assert!(mesh.remove_vertex(verts[1], RemovalPolicy::Cascade).is_ok());
+    let remaining: Vec<FaceIndex> = mesh.faces().collect();
+    let corners: Vec<Vec<VertexIndex>> = remaining.iter()
+        .map(|&f| mesh.vertices(mesh.face(f)).collect())
+        .collect();
+
+    let remap = mesh.compact();
+    assert_eq!(mesh.vertex_list.len(), 8);
+    assert_eq!(mesh.edge_list.len(), 19);
+    assert_eq!(mesh.face_list.len(), 4);
+    assert_eq!(remap.vertices.len(), 9);
+    assert!(!remap.vertex(verts[1]).is_valid());
+    assert!(!mesh.is_current_vertex(verts[7]));
+    let report = mesh.check_integrity();
+    assert!(!report.violations.iter().any(|v| matches!(v, Violation::StaleIndex(_))), "{:?}", report);
+
+    let ids = mesh.attribute::<usize>(AttributeDomain::Vertex, "id").unwrap();
+    assert_eq!(ids.len(), 8);
+    for &v in verts.iter().filter(|&&v| v != verts[1]) {
+        assert!(mesh.is_current_vertex(remap.vertex(v)));
+        assert_eq!(ids[remap.vertex(v)], v.0);
+    }
+    let moved: Vec<FaceIndex> = remaining.iter().map(|&f| remap.face(f)).collect();
+    assert_eq!(mesh.faces().collect::<Vec<_>>(), moved);
+    for ((&face, &old), corners) in moved.iter().zip(&remaining).zip(&corners) {
+        assert_eq!(mesh.attribute::<usize>(AttributeDomain::Face, "id").unwrap()[face], old.0);
+        let remapped: Vec<VertexIndex> = corners.iter().map(|&v| remap.vertex(v)).collect();
+        assert_eq!(mesh.vertices(mesh.face(face)).collect::<Vec<_>>(), remapped);
+    }
+
+    // Without tombstones compacting changes nothing
+    let remap = mesh.compact();
+    assert!(remap.vertices[1 ..].iter().all(|&v| mesh.is_current_vertex(v)));
+    assert_eq!(remap.face(moved[0]), moved[0]);
+}
+
+#[test]
+fn swap_remove_rejects_tombstones() {
+    let (mut mesh, verts, faces) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.set_removal_mode(RemovalMode::Tombstone);
+    mesh.remove_face(faces[5]);
+    assert!(mesh.remove_vertex(verts[7], RemovalPolicy::Cascade).is_ok());
+    let edge = mesh.edge_index_at(mesh.edge_list.len() - 1);
+    let dead_edges = mesh.edge_list.len() - 1 - mesh.edge_count();
+    assert!(dead_edges > 0 && !mesh.is_current_edge(edge));
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (7, 3));
+
+    // The tombstones at the end of each list can't be removed a second time
+    mesh.set_removal_mode(RemovalMode::SwapRemove);
+    assert_eq!(mesh.try_remove_face(faces[5]), Err(Error::InvalidFaceIndex(faces[5])));
+    assert_eq!(mesh.try_remove_edge(edge), Err(Error::InvalidEdgeIndex(edge)));
+    assert_eq!(mesh.remove_vertex(verts[7], RemovalPolicy::Cascade), Err(Error::InvalidVertexIndex(verts[7])));
+    mesh.remove_face(faces[5]);
+    mesh.remove_edge(edge);
+    assert_eq!(mesh.face_list.len(), 7);
+    assert_eq!(mesh.edge_list.len() - 1 - mesh.edge_count(), dead_edges);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (7, 3));
+
+    // Removing a live component moves the tombstone at the end into its slot
+    let remaining: Vec<FaceIndex> = mesh.faces().collect();
+    mesh.remove_face(remaining[0]);
+    assert_eq!(mesh.face_count(), 2);
+    assert_eq!(mesh.faces().count(), 2);
+    mesh.compact();
+    assert_eq!(mesh.face_list.len(), 3);
+    assert_eq!(mesh.vertex_list.len(), 8);
+}
+
+#[test]
+fn narrow_indices_work_like_wide_ones() {
+    assert!(mem::size_of::<Edge<(), u16>>() < mem::size_of::<Edge<(), u32>>());
+    assert!(mem::size_of::<Edge<(), u32>>() < mem::size_of::<Edge<(), usize>>());
+    assert_eq!(mem::size_of::<EdgeIndex<u16>>(), 4);
+
+    let faces: Vec<&[usize]> = CUBE_FACES.iter().map(|f| &f[..]).collect();
+    let mut mesh = Mesh::<(), (), (), u16>::from_indexed_faces(8, &faces).unwrap();
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+
+    let eindex = mesh.face_fn(mesh.faces().next().unwrap()).edge().index;
+    let middle = mesh.split_edge(eindex, 0.5).unwrap();
+    assert_eq!(middle, VertexIndex(9, 0));
+    let subdivided = subdivide::catmull_clark(&mesh, 1).unwrap();
+    assert_eq!(subdivided.faces().count(), 26);
+    let report = subdivided.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn index_overflow_is_reported() {
+    let mut mesh = Mesh::<(), (), (), u16>::default();
+    for _ in 0 .. u16::MAX {
+        mesh.try_add_vertex(Vertex::default()).unwrap();
+    }
+    assert_eq!(mesh.try_add_vertex(Vertex::default()), Err(Error::IndexOverflow));
+    assert_eq!(mesh.try_add_vertex_at(Position::new(0.0, 0.0, 0.0)), Err(Error::IndexOverflow));
+    assert_eq!(mesh.vertex_list.len(), u16::MAX as usize + 1);
+
+    let verts = [VertexIndex(1, 0), VertexIndex(2, 0), VertexIndex(3, 0)];
+    for _ in 0 .. u16::MAX / 3 {
+        mesh.try_add_polygon(&verts).unwrap();
+    }
+    assert_eq!(mesh.try_add_polygon(&verts), Err(Error::IndexOverflow));
+    assert_eq!(mesh.try_add_edge(Edge::default()), Err(Error::IndexOverflow));
+    while mesh.face_list.len() <= u16::MAX as usize {
+        mesh.try_add_face(Face::default()).unwrap();
+    }
+    assert_eq!(mesh.try_add_face(Face::default()), Err(Error::IndexOverflow));
+
+    let too_many = Mesh::<(), (), (), u16>::from_indexed_faces(u16::MAX as usize + 1, &[&[0, 1, 2]]);
+    assert_eq!(too_many.unwrap_err(), Error::IndexOverflow);
+    let out_of_range: &[usize] = &[0, 1, u16::MAX as usize];
+    let too_large = Mesh::<(), (), (), u16>::from_indexed_faces(3, &[out_of_range]);
+    assert_eq!(too_large.unwrap_err(), Error::IndexOverflow);
+
+    // The sixth level of subdivision has more edges than u16 indices address
+    let faces: Vec<&[usize]> = CUBE_FACES.iter().map(|f| &f[..]).collect();
+    let cube = Mesh::<(), (), (), u16>::from_indexed_faces(8, &faces).unwrap();
+    assert_eq!(subdivide::catmull_clark(&cube, 6).unwrap_err(), Error::IndexOverflow);
+}
+
+#[test]
+fn generations_wrap_around_at_the_index_range() {
+    let mut mesh = Mesh::<(), (), (), u16>::default();
+    let first = mesh.add_vertex(Vertex::default());
+    let mut vindex = first;
+    for _ in 0 .. u16::MAX {
+        mesh.remove_vertex(vindex, RemovalPolicy::Refuse).unwrap();
+        vindex = mesh.add_vertex(Vertex::default());
+        assert!(!mesh.is_current_vertex(first));
+    }
+    assert_eq!(vindex, VertexIndex(1, u16::MAX));
+
+    // One more removal exhausts the generations of the slot, after which the
+    // first index refers to whichever vertex holds it again
+    mesh.remove_vertex(vindex, RemovalPolicy::Refuse).unwrap();
+    let last = mesh.add_vertex(Vertex::default());
+    assert_eq!(last, first);
+    assert!(mesh.is_current_vertex(first));
+    assert!(!mesh.is_current_vertex(vindex));
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn reading_obj_reports_index_overflow() {
+    let source = "v 0 0 0\n".repeat(u16::MAX as usize + 1);
+    match obj::read_with_index_type::<u16, _>(source.as_bytes()) {
+        Err(obj::ObjError::Mesh { line, error: Error::IndexOverflow }) => assert_eq!(line, u16::MAX as usize + 1),
+        other => panic!("unexpected result {:?}", other.map(|obj| obj.mesh.vertex_count())),
+    }
+}
+
+#[test]
+fn payloads_move_with_their_components() {
+    let mut mesh: Mesh<u32, (), &str> = Mesh::default();
+    let verts: Vec<VertexIndex> = (1 .. 5)
+        .map(|i| mesh.add_vertex(Vertex { data: i, ..Vertex::default() }))
+        .collect();
+    let face = mesh.add_triangle(verts[0], verts[1], verts[2]);
+    mesh.face_mut(face).unwrap().data = "tri";
+    assert_eq!(*mesh.face_fn(face).data(), "tri");
+    assert_eq!(*mesh.face_fn(face).edge().next().vertex().data(), 2);
+    assert_eq!(*mesh.face_fn(face).edge().data(), ());
+
+    let eindex = mesh.face_fn(face).edge().index;
+    let middle = mesh.split_edge(eindex, 0.5).unwrap();
+    assert_eq!(*mesh.vertex_fn(middle).data(), 0);
+
+    // The last vertex moves into the removed slot along with its payload
+    mesh.remove_vertex(middle, RemovalPolicy::Cascade).unwrap();
+    mesh.remove_vertex(verts[1], RemovalPolicy::Cascade).unwrap();
+    let moved = mesh.vertex_index_at(verts[1].0);
+    assert_eq!(*mesh.vertex_fn(moved).data(), 4);
+    assert_eq!(mesh.vertex_count(), 3);
+}
+
+#[test]
+fn mutable_function_sets_navigate_and_edit() {
+    let (mut mesh, verts, _) = build_mesh(&CUBE_POSITIONS, &CUBE_FACES);
+    mesh.triangulate_all(Triangulation::Fan).unwrap();
+    mesh.add_attribute::<f32>(AttributeDomain::Face, "weight");
+    let diagonal = mesh.find_edge(verts[0], verts[2]).or_else(|| mesh.find_edge(verts[2], verts[0])).unwrap();
+    let twin_face = mesh.edge_fn(diagonal).twin().face().index;
+    let far_index = mesh.face_fn(twin_face).edge().prev().vertex().index;
+
+    let mut face = mesh.edge_fn_mut(diagonal).unwrap().twin().unwrap().face().unwrap();
+    face.set_attribute("weight", 2.0f32).unwrap();
+    assert_eq!(face.set_attribute("missing", 1.0f32), Err(Error::UnknownAttribute));
+    let mut far = face.edge().unwrap().prev().unwrap().vertex().unwrap();
+    assert_eq!(far.index, far_index);
+    far.set_position(Position::new(-1.0, -1.0, -1.0)).unwrap();
+    assert_eq!(*mesh.position(far_index), Position::new(-1.0, -1.0, -1.0));
+    assert_eq!(mesh.attribute::<f32>(AttributeDomain::Face, "weight").unwrap()[twin_face], 2.0);
+
+    // Flipping turns the diagonal of the bottom face into the other one
+    let mut edge = mesh.edge_fn_mut(diagonal).unwrap();
+    edge.flip().unwrap();
+    assert!([verts[1], verts[3]].contains(&edge.as_fn().vertex().index));
+    let middle = edge.split(0.5).unwrap();
+    assert_eq!(*middle.position(), Position::new(0.5, 0.5, 0.0));
+    let merged = middle.edge().unwrap().collapse(Position::new(0.5, 0.5, 0.0)).unwrap();
+    assert!(merged.is_valid());
+    assert_eq!(*merged.position(), Position::new(0.5, 0.5, 0.0));
+    let merged_index = merged.index;
+    let report = mesh.check_integrity();
+    assert!(report.is_valid(), "{:?}", report);
+
+    // No function set exists for stale or default indices
+    assert!(mesh.edge_fn_mut(EdgeIndex(42, 0)).is_none());
+    assert!(mesh.face_fn_mut(FaceIndex::default()).is_none());
+    mesh.set_removal_mode(RemovalMode::Tombstone);
+    let spoke = mesh.vertex_fn(merged_index).edge().index;
+    let outer = mesh.edge_fn(spoke).next().index;
+    let removed_face = mesh.edge_fn(spoke).face().index;
+    mesh.remove_face(removed_face);
+    assert!(mesh.face_fn_mut(removed_face).is_none());
+    assert!(mesh.edge_fn_mut(outer).unwrap().face().is_none());
+}
@@ -0,0 +1,145 @@
+//!
+//! Splitting polygons into triangles.
+//!
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+
+use super::*;
+
+
+/// The method used to triangulate polygons.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Triangulation {
+    /// Connect the first vertex of the polygon to every other vertex.
+    ///
+    /// This is only correct for convex polygons, but doesn't depend on
+    /// vertex positions.
+    Fan,
+    /// Repeatedly cut off the first convex corner which doesn't contain any
+    /// other vertex of the polygon, projecting it onto the plane given by
+    /// its normal. This handles concave polygons as well.
+    EarClipping,
+}
+
+fn cross(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Returns true when `p` lies inside the counter-clockwise triangle
+/// `a, b, c` or on its border.
+fn triangle_contains(a: Vector2<f32>, b: Vector2<f32>, c: Vector2<f32>, p: Vector2<f32>) -> bool {
+    cross(b - a, p - a) >= 0.0 && cross(c - b, p - b) >= 0.0 && cross(a - c, p - c) >= 0.0
+}
+
+impl<V, E, F, Ix: IndexType> Mesh<V, E, F, Ix> {
+    /// Splits a face into triangles, returning the indices of all of them,
+    /// starting with the original face which becomes one of the triangles.
+    ///
+    /// Triangles are cut off like in `split_face`, so the edges of the
+    /// original face keep their indices and twins, the new edges copy the
+    /// attribute values of the corners they start at, and the new faces copy
+    /// the values of the original one.
+    ///
+    /// Ear clipping falls back to a fan for faces without a normal.
+    ///
+    /// ```
+    /// use hedge::{Mesh, Position, Triangulation};
+    /// let mut mesh = Mesh::new();
+    ///
+    /// let v1 = mesh.add_vertex_at(Position::new(0.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex_at(Position::new(2.0, 0.0, 0.0));
+    /// let v3 = mesh.add_vertex_at(Position::new(1.0, 1.0, 0.0));
+    /// let v4 = mesh.add_vertex_at(Position::new(2.0, 2.0, 0.0));
+    /// let v5 = mesh.add_vertex_at(Position::new(0.0, 2.0, 0.0));
+    /// let f1 = mesh.add_polygon(&[v1, v2, v3, v4, v5]);
+    ///
+    /// let triangles = mesh.triangulate_face(f1, Triangulation::EarClipping).unwrap();
+    /// assert_eq!(triangles.len(), 3);
+    /// ```
+    pub fn triangulate_face(&mut self, index: FaceIndex<Ix>, method: Triangulation) -> Result<Vec<FaceIndex<Ix>>, Error<Ix>>
+        where E: Default, F: Default
+    {
+        self.check_face_index(index)?;
+        let root = self.face(index).edge_index;
+        self.check_edge_index(root)?;
+        if !self.loop_is_closed(root) {
+            return Err(Error::OpenEdgeLoop(root));
+        }
+
+        let corners: Vec<EdgeIndex<Ix>> = self.edges(self.face(index)).collect();
+        let normal = self.face_fn(index).normal();
+        let mut result = vec![index];
+        if method == Triangulation::EarClipping && normal.magnitude2() > 0.0 {
+            self.clip_ears(corners, normal, &mut result);
+        } else {
+            self.fan(corners, &mut result);
+        }
+        Ok(result)
+    }
+
+    /// Splits every face of the mesh into triangles.
+    pub fn triangulate_all(&mut self, method: Triangulation) -> Result<(), Error<Ix>>
+        where E: Default, F: Default
+    {
+        let faces: Vec<FaceIndex<Ix>> = self.faces().collect();
+        for findex in faces {
+            self.triangulate_face(findex, method)?;
+        }
+        Ok(())
+    }
+
+    /// Cuts triangles off the corners of a face, where `corners` are the
+    /// edges of its loop in order.
+    fn fan(&mut self, mut corners: Vec<EdgeIndex<Ix>>, triangles: &mut Vec<FaceIndex<Ix>>)
+        where E: Default, F: Default
+    {
+        while corners.len() > 3 {
+            let (new_face, _) = self.insert_diagonal(corners[0], corners[2]);
+            triangles.push(new_face);
+            corners[0] = self.edge(corners[2]).prev_index;
+            corners.remove(1);
+        }
+    }
+
+    fn clip_ears(&mut self, mut corners: Vec<EdgeIndex<Ix>>, normal: Normal, triangles: &mut Vec<FaceIndex<Ix>>)
+        where E: Default, F: Default
+    {
+        // Project onto the plane of the face, keeping the loop counter-clockwise.
+        let axis = if normal.x.abs() > 0.9 {
+            Vector3::new(0.0, 1.0, 0.0)
+        } else {
+            Vector3::new(1.0, 0.0, 0.0)
+        };
+        let u = axis.cross(normal).normalize();
+        let v = normal.cross(u);
+        let mut points: Vec<Vector2<f32>> = corners.iter()
+            .map(|&eindex| {
+                let p = *self.edge_fn(eindex).vertex().position() - Position::new(0.0, 0.0, 0.0);
+                Vector2::new(p.dot(u), p.dot(v))
+            })
+            .collect();
+
+        while corners.len() > 3 {
+            let n = corners.len();
+            let is_ear = |i: usize| {
+                let (a, b, c) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+                cross(b - a, c - b) > 0.0 && (0 .. n)
+                    .filter(|&j| j != i && j != (i + n - 1) % n && j != (i + 1) % n)
+                    .all(|j| {
+                        let p = points[j];
+                        p == a || p == b || p == c || !triangle_contains(a, b, c, p)
+                    })
+            };
+            // Without an ear the polygon is degenerate, so any corner will do.
+            let i = (0 .. n).find(|&i| is_ear(i)).unwrap_or(0);
+            let prev = (i + n - 1) % n;
+            let next = (i + 1) % n;
+
+            let (new_face, _) = self.insert_diagonal(corners[prev], corners[next]);
+            triangles.push(new_face);
+            corners[prev] = self.edge(corners[next]).prev_index;
+            corners.remove(i);
+            points.remove(i);
+        }
+    }
+}